@@ -0,0 +1,77 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Badge generation throughput. Run `cargo bench -- --save-baseline <name>` on two
+//! revisions and compare them with `--baseline <name>` to see the effect of a change.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rsbadges::{Badge, Style};
+
+/// `badge` in every style, with the name of each style for the benchmark IDs.
+fn all_styles(badge: Badge) -> Vec<(&'static str, Style)> {
+    vec![
+        ("flat", Style::Flat(badge.clone())),
+        ("flat_square", Style::FlatSquare(badge.clone())),
+        ("plastic", Style::Plastic(badge.clone())),
+        ("for_the_badge", Style::ForTheBadge(badge.clone())),
+        ("social", Style::Social(badge)),
+    ]
+}
+
+/// Labels made of printable ASCII, which are measured from the width tables.
+fn bench_ascii(c: &mut Criterion) {
+    let badge = Badge {
+        label_text: String::from("coverage"),
+        msg_text: String::from("87.3%"),
+        ..Badge::default()
+    };
+    let mut group = c.benchmark_group("ascii");
+    for (name, style) in all_styles(badge) {
+        group.bench_function(name, |b| b.iter(|| black_box(&style).generate_svg()));
+    }
+    group.finish();
+}
+
+/// Labels outside of printable ASCII, which miss the width tables and are shaped, or
+/// with only `glyph-metrics`, laid out glyph by glyph.
+fn bench_non_ascii(c: &mut Criterion) {
+    let badge = Badge {
+        label_text: String::from("röck döts"),
+        msg_text: String::from("不知道"),
+        ..Badge::default()
+    };
+    let mut group = c.benchmark_group("non_ascii");
+    for (name, style) in all_styles(badge) {
+        group.bench_function(name, |b| b.iter(|| black_box(&style).generate_svg()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ascii, bench_non_ascii);
+criterion_main!(benches);
@@ -29,11 +29,33 @@
 
 //! All types and functions needed to create a Badge of any style
 
+mod badge_grid;
 mod badge_type;
-mod format_helper;
+mod color_scale;
+mod data_uri;
+pub(crate) mod format_helper;
 mod generate_layout;
 mod generate_svg;
+mod gradient;
+mod snippet;
 mod style;
+mod style_metrics;
+#[cfg(feature = "shaping")]
+mod text_shaping;
+#[cfg(feature = "validate")]
+mod validate_svg;
 
-pub use badge_type::{Badge, BadgeError};
+#[cfg(not(any(feature = "shaping", feature = "glyph-metrics")))]
+compile_error!("Text can't be measured: enable the `shaping` or `glyph-metrics` feature.");
+
+pub use badge_grid::{Alignment, BadgeGrid};
+pub use badge_type::{
+    Badge, BadgeError, BadgeLayout, ContrastMode, ContrastReport, Rect, Segment, SegmentBox,
+    SideContrast, TextBox,
+};
+pub use color_scale::ColorScale;
+pub use data_uri::{svg_data_uri, DataUriEncoding};
+pub use snippet::SnippetFormat;
 pub use style::Style;
+#[cfg(feature = "validate")]
+pub use validate_svg::validate_svg;
@@ -0,0 +1,199 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Several badges laid out in a single SVG
+
+use super::badge_type::BadgeError;
+use super::generate_layout;
+use super::generate_svg::{self, GridCell};
+use super::style::Style;
+
+/// Where a badge sits in its cell of a [BadgeGrid], when the cell is larger than it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Left of the column, or top of the row
+    Start,
+    /// Centered in the column or row
+    Center,
+    /// Right of the column, or bottom of the row
+    End,
+}
+
+impl Alignment {
+    /// The offset of something `size` long in a cell `cell_size` long.
+    fn offset(self, size: f32, cell_size: f32) -> f32 {
+        match self {
+            Alignment::Start => 0.0,
+            Alignment::Center => (cell_size - size) / 2.0,
+            Alignment::End => cell_size - size,
+        }
+    }
+}
+
+/// Several badges laid out in a single SVG, in one row or in a grid.
+///
+/// Badges are placed left to right, then top to bottom. Each column is as wide as
+/// its widest badge and each row as tall as its tallest, so badges of different
+/// styles line up. Every badge keeps its own links, titles and dark mode colors,
+/// and the IDs inside each badge stay unique across the grid.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::{Badge, BadgeGrid, Style};
+/// let grid = BadgeGrid {
+///     badges: vec![
+///         Style::Flat(Badge::default()),
+///         Style::ForTheBadge(Badge::default()),
+///     ],
+///     column_gap: 8.0,
+///     ..BadgeGrid::default()
+/// };
+/// let svg = grid.generate_svg().unwrap();
+/// ```
+///
+#[derive(Debug)]
+pub struct BadgeGrid {
+    /// The badges to lay out, in order
+    pub badges: Vec<Style>,
+    /// The number of badges in each row. 0 puts every badge in a single row.
+    pub columns: usize,
+    /// The space between columns, in px
+    pub column_gap: f32,
+    /// The space between rows, in px
+    pub row_gap: f32,
+    /// How badges narrower than their column are placed in it
+    pub horizontal_alignment: Alignment,
+    /// How badges shorter than their row are placed in it
+    pub vertical_alignment: Alignment,
+}
+
+impl Default for BadgeGrid {
+    fn default() -> BadgeGrid {
+        BadgeGrid {
+            badges: Vec::new(),
+            columns: 0,
+            column_gap: 4.0,
+            row_gap: 4.0,
+            horizontal_alignment: Alignment::Start,
+            vertical_alignment: Alignment::Center,
+        }
+    }
+}
+
+impl BadgeGrid {
+    /// Generates a single SVG holding every badge of the grid.
+    ///
+    /// # Errors
+    ///
+    /// The same as [generate_svg](Style::generate_svg), for any of the badges.
+    ///
+    pub fn generate_svg(&self) -> Result<String, BadgeError> {
+        self.render(&generate_svg::random_id_suffix())
+    }
+
+    /// Generates the SVG of the grid like [generate_svg](BadgeGrid::generate_svg), but
+    /// ends every ID with `id_suffix` and the index of its badge, instead of a random
+    /// suffix, so the same grid always generates the same SVG.
+    ///
+    /// # Errors
+    ///
+    /// The same as [Style::generate_svg_with_id_suffix], for any of the badges.
+    ///
+    pub fn generate_svg_with_id_suffix(&self, id_suffix: &str) -> Result<String, BadgeError> {
+        generate_svg::check_id_suffix(id_suffix)?;
+        self.render(id_suffix)
+    }
+
+    /// Lay out and render every badge once, each with its own ID suffix, and place
+    /// them in the grid.
+    fn render(&self, id_suffix: &str) -> Result<String, BadgeError> {
+        let columns = match self.columns {
+            0 => self.badges.len().max(1),
+            columns => columns,
+        };
+        let layouts = self
+            .badges
+            .iter()
+            .map(Style::checked_layout)
+            .collect::<Result<Vec<_>, _>>()?;
+        let sizes: Vec<(f32, f32)> = layouts
+            .iter()
+            .map(|layout| {
+                let layout = generate_layout::badge_layout(layout);
+                (layout.width, layout.height)
+            })
+            .collect();
+
+        let rows = sizes.len().div_ceil(columns);
+        let mut column_widths = vec![0.0_f32; columns.min(sizes.len())];
+        let mut row_heights = vec![0.0_f32; rows];
+        for (i, (width, height)) in sizes.iter().enumerate() {
+            column_widths[i % columns] = column_widths[i % columns].max(*width);
+            row_heights[i / columns] = row_heights[i / columns].max(*height);
+        }
+        let starts = |sizes: &[f32], gap: f32| {
+            sizes
+                .iter()
+                .scan(0.0, |start, size| {
+                    let this = *start;
+                    *start += size + gap;
+                    Some(this)
+                })
+                .collect::<Vec<f32>>()
+        };
+        let column_x = starts(&column_widths, self.column_gap);
+        let row_y = starts(&row_heights, self.row_gap);
+
+        let mut cells = Vec::with_capacity(self.badges.len());
+        for (i, ((style, layout), (width, height))) in
+            self.badges.iter().zip(layouts).zip(&sizes).enumerate()
+        {
+            let (column, row) = (i % columns, i / columns);
+            cells.push(GridCell {
+                // The index keeps the IDs of each badge apart from the others
+                svg: style.render(layout, &format!("{}_g{}", id_suffix, i))?,
+                x: column_x[column]
+                    + self
+                        .horizontal_alignment
+                        .offset(*width, column_widths[column]),
+                y: row_y[row] + self.vertical_alignment.offset(*height, row_heights[row]),
+            });
+        }
+
+        let total = |sizes: &[f32], gap: f32| {
+            sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32
+        };
+        Ok(generate_svg::grid_svg(
+            &cells,
+            total(&column_widths, self.column_gap),
+            total(&row_heights, self.row_gap),
+        ))
+    }
+}
@@ -29,6 +29,8 @@
 
 //! Used to set the generic info present on the badge.
 
+use super::color_scale::ColorScale;
+use super::gradient::Gradient;
 use thiserror::Error;
 
 /// Holds generic info about a badge.
@@ -67,6 +69,15 @@ pub struct Badge {
     /// let valid_html_color = String::from("white");
     /// ```
     ///
+    /// CSS `linear-gradient(...)` syntax draws a gradient instead. It takes an optional
+    /// direction, such as `to right` or `45deg`, followed by two or more colors, each
+    /// with an optional percentage position. Light or dark text is picked from the
+    /// average brightness of the gradient.
+    ///
+    /// ```
+    /// let valid_gradient = String::from("linear-gradient(to right, #8a2be2, orange 70%)");
+    /// ```
+    ///
     pub label_color: String,
     /// The url to redirect to when the left side of the badge is clicked.
     pub label_link: String,
@@ -76,6 +87,11 @@ pub struct Badge {
     pub msg_color: String,
     /// The url to redirect to when the right side of the badge is clicked.
     pub msg_link: String,
+    /// Further segments to show after the message, for badges with more than two, such
+    /// as `build | linux | passing | 2m31s`. See [Segment] for their options.
+    ///
+    /// Extra segments are styled like the message.
+    pub extra_segments: Vec<Segment>,
     /// A URI reference to a logo to display in the badge. Must be in SVG format.
     ///
     /// This argument will accept both logo URLs and local logo files.
@@ -102,11 +118,69 @@ pub struct Badge {
     pub badge_link: String,
     /// The title to associate with the entire badge. More info
     /// [here](https://developer.mozilla.org/en-US/docs/Web/SVG/Element/title).
+    ///
+    /// Leave this empty to use the accessible name of the badge (see
+    /// [aria_label](Badge::aria_label)) as its title.
     pub badge_title: String,
     /// The title to associate with the left side of the badge.
     pub label_title: String,
     /// The title to associate with the right side of the badge.
     pub msg_title: String,
+    /// The accessible name of the badge, read out by screen readers. More info
+    /// [here](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-label).
+    ///
+    /// Leave this empty to use the badge content, in the form "label: message".
+    pub aria_label: String,
+    /// The text color of the left side of the badge. See [label_color](Badge::label_color)
+    /// for valid formats.
+    ///
+    /// Leave this empty to pick light or dark text automatically, whichever reads
+    /// best on the background color.
+    pub label_text_color: String,
+    /// The text color of the right side of the badge. See
+    /// [label_text_color](Badge::label_text_color) for more info.
+    pub msg_text_color: String,
+    /// The color of the shadow under the text on the left side of the badge. See
+    /// [label_color](Badge::label_color) for valid formats.
+    ///
+    /// Leave this empty to pick a shadow automatically from the background color.
+    /// Only the flat, plastic, and social styles draw shadows.
+    pub label_shadow_color: String,
+    /// The color of the shadow under the text on the right side of the badge. See
+    /// [label_shadow_color](Badge::label_shadow_color) for more info.
+    pub msg_shadow_color: String,
+    /// Draw the text without shadows.
+    pub hide_shadows: bool,
+    /// How to handle text that doesn't contrast enough with its background to be
+    /// readable. See [ContrastMode] for the options.
+    pub contrast_mode: ContrastMode,
+    /// The color of the page the badge will be shown on. See
+    /// [label_color](Badge::label_color) for valid formats.
+    ///
+    /// Badge colors may be translucent. They are blended over this color to decide
+    /// on text colors and to measure contrast.
+    pub page_color: String,
+    /// The background color of the left side of the badge when the viewer prefers a
+    /// dark color scheme. See [label_color](Badge::label_color) for valid formats,
+    /// apart from gradients.
+    ///
+    /// Dark colors are applied with a `@media (prefers-color-scheme: dark)` style in
+    /// the SVG, so they only take effect where the badge is displayed inline, or
+    /// in the dark file of [generate_svg_pair](crate::Style::generate_svg_pair).
+    /// Leave all of the dark colors empty to generate a badge without this style.
+    pub dark_label_color: String,
+    /// The background color of the right side of the badge in dark mode. See
+    /// [dark_label_color](Badge::dark_label_color) for more info.
+    pub dark_msg_color: String,
+    /// The text color of the left side of the badge in dark mode. See
+    /// [dark_label_color](Badge::dark_label_color) for more info.
+    ///
+    /// Leave this empty to pick light or dark text automatically from
+    /// [dark_label_color](Badge::dark_label_color), if it is set.
+    pub dark_label_text_color: String,
+    /// The text color of the right side of the badge in dark mode. See
+    /// [dark_label_text_color](Badge::dark_label_text_color) for more info.
+    pub dark_msg_text_color: String,
     /// This setting only applies when a logo is supplied AND the label is empty.
     /// If this setting is true, then the logo will use the label background color.
     /// If it is false, it will use the message background color.
@@ -121,6 +195,7 @@ impl Default for Badge {
             badge_link: String::from(""),
             label_link: String::from(""),
             msg_link: String::from(""),
+            extra_segments: Vec::new(),
             label_color: String::from("#555"),
             msg_color: String::from("#007ec6"),
             logo: String::from(""),
@@ -128,11 +203,305 @@ impl Default for Badge {
             badge_title: String::from(""),
             label_title: String::from(""),
             msg_title: String::from(""),
+            aria_label: String::from(""),
+            label_text_color: String::from(""),
+            msg_text_color: String::from(""),
+            label_shadow_color: String::from(""),
+            msg_shadow_color: String::from(""),
+            hide_shadows: false,
+            contrast_mode: ContrastMode::Ignore,
+            page_color: String::from("#fff"),
+            dark_label_color: String::from(""),
+            dark_msg_color: String::from(""),
+            dark_label_text_color: String::from(""),
+            dark_msg_text_color: String::from(""),
             use_logo_as_label: false,
         }
     }
 }
 
+impl Badge {
+    /// Create a badge from its segments, in order from left to right. The first
+    /// segment becomes the label, the second the message, and the rest
+    /// [extra_segments](Badge::extra_segments). Everything else is left at its default.
+    ///
+    /// Segment colors that are left empty keep their default.
+    ///
+    /// ```
+    /// use rsbadges::{Badge, Segment};
+    /// let badge = Badge::from_segments(vec![
+    ///     Segment::new("build"),
+    ///     Segment::new("linux"),
+    ///     Segment {
+    ///         color: String::from("#4c1"),
+    ///         ..Segment::new("passing")
+    ///     },
+    ///     Segment::new("2m31s"),
+    /// ]);
+    /// assert_eq!(badge.msg_text, "linux");
+    /// assert_eq!(badge.segments().len(), 4);
+    /// ```
+    pub fn from_segments(segments: Vec<Segment>) -> Badge {
+        let mut segments = segments.into_iter();
+        let default = Badge::default();
+        let label = segments.next().unwrap_or_default();
+        let msg = segments.next().unwrap_or_default();
+        let or_default = |color: String, default: String| {
+            if color.is_empty() {
+                default
+            } else {
+                color
+            }
+        };
+        Badge {
+            label_text: label.text,
+            label_color: or_default(label.color, default.label_color.clone()),
+            label_link: label.link,
+            label_title: label.title,
+            label_text_color: label.text_color,
+            label_shadow_color: label.shadow_color,
+            dark_label_color: label.dark_color,
+            dark_label_text_color: label.dark_text_color,
+            msg_text: msg.text,
+            msg_color: or_default(msg.color, default.msg_color.clone()),
+            msg_link: msg.link,
+            msg_title: msg.title,
+            msg_text_color: msg.text_color,
+            msg_shadow_color: msg.shadow_color,
+            dark_msg_color: msg.dark_color,
+            dark_msg_text_color: msg.dark_text_color,
+            extra_segments: segments.collect(),
+            ..default
+        }
+    }
+
+    /// Create a badge that shows `value` as a percentage, such as `87.3%`, with the
+    /// message colored by `scale`. Everything else is left at its default.
+    ///
+    /// ```
+    /// use rsbadges::{Badge, ColorScale};
+    /// let badge = Badge::percentage("coverage", 87.3, &ColorScale::coverage()).unwrap();
+    /// assert_eq!(badge.msg_text, "87.3%");
+    /// assert_eq!(badge.msg_color, "#97ca00");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [ColorScale::color_for].
+    ///
+    pub fn percentage(label: &str, value: f32, scale: &ColorScale) -> Result<Badge, BadgeError> {
+        Ok(Badge {
+            label_text: String::from(label),
+            msg_text: format_percentage(value),
+            msg_color: scale.color_for(value)?,
+            ..Badge::default()
+        })
+    }
+
+    /// All of the segments of the badge, from left to right: the label, the message,
+    /// and then any [extra_segments](Badge::extra_segments).
+    pub fn segments(&self) -> Vec<Segment> {
+        let label = Segment {
+            text: self.label_text.clone(),
+            color: self.label_color.clone(),
+            link: self.label_link.clone(),
+            title: self.label_title.clone(),
+            text_color: self.label_text_color.clone(),
+            shadow_color: self.label_shadow_color.clone(),
+            dark_color: self.dark_label_color.clone(),
+            dark_text_color: self.dark_label_text_color.clone(),
+        };
+        let msg = Segment {
+            text: self.msg_text.clone(),
+            color: self.msg_color.clone(),
+            link: self.msg_link.clone(),
+            title: self.msg_title.clone(),
+            text_color: self.msg_text_color.clone(),
+            shadow_color: self.msg_shadow_color.clone(),
+            dark_color: self.dark_msg_color.clone(),
+            dark_text_color: self.dark_msg_text_color.clone(),
+        };
+        [label, msg]
+            .into_iter()
+            .chain(self.extra_segments.iter().cloned())
+            .collect()
+    }
+}
+
+/// Format a percentage with at most one decimal, such as `87.3%` or `100%`.
+fn format_percentage(value: f32) -> String {
+    let rounded = format!("{:.1}", value);
+    format!("{}%", rounded.strip_suffix(".0").unwrap_or(&rounded))
+}
+
+/// One segment of a badge, with its own text, colors, link and title.
+///
+/// [Badge] holds the label and message segments in its own fields, and any others
+/// in [extra_segments](Badge::extra_segments). Each option works like its label
+/// counterpart on [Badge]; leave an option empty to skip it, or to pick its color
+/// automatically.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Segment {
+    /// The text to show in the segment.
+    pub text: String,
+    /// The background color of the segment. See [label_color](Badge::label_color)
+    /// for valid formats. An extra segment left empty gets the default message color.
+    pub color: String,
+    /// The url to redirect to when the segment is clicked.
+    pub link: String,
+    /// The title of the segment, shown as a tooltip.
+    pub title: String,
+    /// The text color of the segment.
+    pub text_color: String,
+    /// The color of the shadow under the text of the segment.
+    pub shadow_color: String,
+    /// The background color of the segment in dark mode. See
+    /// [dark_label_color](Badge::dark_label_color) for more info.
+    pub dark_color: String,
+    /// The text color of the segment in dark mode.
+    pub dark_text_color: String,
+}
+
+impl Segment {
+    /// Create a segment with the given text, and every other option left empty.
+    pub fn new(text: &str) -> Segment {
+        Segment {
+            text: String::from(text),
+            ..Segment::default()
+        }
+    }
+}
+
+/// How badge generation handles text that contrasts too little with its background.
+///
+/// The threshold is the WCAG 2.x level AA contrast ratio for normal text, 4.5:1
+/// (see [SideContrast::AA_RATIO]). Use [Style::contrast_report](crate::Style::contrast_report)
+/// to check the ratios of a badge without enforcing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContrastMode {
+    /// Generate the badge as given, whatever its contrast.
+    #[default]
+    Ignore,
+    /// Fail with [BadgeError::InsufficientContrast] if any text is below AA.
+    Strict,
+    /// Darken or lighten the background of any side whose text is below AA, just
+    /// enough for it to pass. Styles that draw their own backgrounds, like social,
+    /// adjust the text color instead.
+    AutoAdjust,
+}
+
+/// The contrast of each side of a badge, as returned by
+/// [Style::contrast_report](crate::Style::contrast_report).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastReport {
+    /// contrast of the label (left side), if it has text
+    pub label: Option<SideContrast>,
+    /// contrast of the message (right side), if it has text
+    pub message: Option<SideContrast>,
+    /// contrast of each of the [extra_segments](Badge::extra_segments), for those
+    /// that have text
+    pub extra_segments: Vec<Option<SideContrast>>,
+}
+
+impl ContrastReport {
+    /// Whether the text on every side meets WCAG level AA.
+    pub fn passes_aa(&self) -> bool {
+        [&self.label, &self.message]
+            .into_iter()
+            .chain(&self.extra_segments)
+            .all(|side| side.as_ref().is_none_or(SideContrast::passes_aa))
+    }
+}
+
+/// The contrast between the text of one side of a badge and its background.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SideContrast {
+    /// the background color, as written to the SVG
+    pub background: String,
+    /// the text color, as written to the SVG
+    pub text: String,
+    /// the WCAG 2.x contrast ratio, from 1 (no contrast) to 21 (black on white)
+    pub ratio: f32,
+}
+
+impl SideContrast {
+    /// The lowest contrast ratio for normal text to meet WCAG level AA.
+    pub const AA_RATIO: f32 = 4.5;
+    /// The lowest contrast ratio for normal text to meet WCAG level AAA.
+    pub const AAA_RATIO: f32 = 7.0;
+
+    /// Whether the text meets WCAG level AA.
+    pub fn passes_aa(&self) -> bool {
+        self.ratio >= Self::AA_RATIO
+    }
+
+    /// Whether the text meets WCAG level AAA.
+    pub fn passes_aaa(&self) -> bool {
+        self.ratio >= Self::AAA_RATIO
+    }
+}
+
+/// The geometry of a badge, as returned by [Style::layout](crate::Style::layout).
+///
+/// All values are in px, measured from the top left corner of the badge SVG.
+/// This is the same geometry used to render the badge, so it can be used to place
+/// badges precisely within a larger SVG without generating them first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BadgeLayout {
+    /// total width of the badge
+    pub width: f32,
+    /// total height of the badge
+    pub height: f32,
+    /// the background rectangle of the label (left side)
+    pub label: Rect,
+    /// the background rectangle of the message (right side)
+    pub message: Rect,
+    /// placement of the label text
+    pub label_text: TextBox,
+    /// placement of the message text
+    pub msg_text: TextBox,
+    /// placement of each of the [extra_segments](Badge::extra_segments)
+    pub extra_segments: Vec<SegmentBox>,
+    /// the box the logo is drawn into, if the badge has a logo
+    pub logo: Option<Rect>,
+}
+
+/// The placement of one segment of a badge, in px.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SegmentBox {
+    /// the background rectangle of the segment
+    pub rect: Rect,
+    /// placement of the segment text
+    pub text: TextBox,
+}
+
+/// An axis-aligned rectangle within a badge, in px.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    /// placement of the left edge in x
+    pub x: f32,
+    /// placement of the top edge in y
+    pub y: f32,
+    /// width of the rectangle
+    pub width: f32,
+    /// height of the rectangle
+    pub height: f32,
+}
+
+/// The placement of a line of badge text, in px.
+///
+/// Text is drawn centered on `center_x`, so it spans from
+/// `center_x - width / 2` to `center_x + width / 2`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TextBox {
+    /// placement of the text center in x
+    pub center_x: f32,
+    /// placement of the text baseline in y
+    pub baseline_y: f32,
+    /// width of the text, letter spacing included
+    pub width: f32,
+}
+
 #[derive(Default, Debug)]
 /// The formatted layout of a badge.
 ///
@@ -140,30 +509,16 @@ impl Default for Badge {
 /// validated and formatted before being used to create an SVG of the requested
 /// badge type.
 pub(crate) struct Layout {
-    /// text for label (left side)
-    pub label_text_norm: String,
-    /// text for message (right side)
-    pub msg_text_norm: String,
+    /// accessible name of the badge
+    pub aria_label: String,
+    /// title of the entire badge
+    pub badge_title: String,
     /// height of the badge, in px
     pub badge_height: f32,
-    /// width of text for label, in px
-    pub label_text_width: f32,
-    /// width of text for message, in px
-    pub msg_text_width: f32,
-    /// total width of the label side, in px
-    pub label_total_width: f32,
-    /// total width of the message side, in px
-    pub msg_total_width: f32,
-    /// logo text placement in x
-    pub label_text_x: f32,
-    /// message text placement in x
-    pub msg_text_x: f32,
-    /// message bubble placement in x
-    pub msg_bubble_x: f32,
-    /// width of the label rectangle, in px
-    pub label_rect_width: f32,
-    /// width of the message rectangle, in px
-    pub msg_rect_width: f32,
+    /// placement of the text baseline in y, in px
+    pub text_y: f32,
+    /// width of the outline around each segment, in px
+    pub outline_width: f32,
     /// padding of the logo, in px
     pub logo_padding: f32,
     /// width of the logo, in px
@@ -172,18 +527,69 @@ pub(crate) struct Layout {
     pub logo_x: f32,
     /// logo placement in y
     pub logo_y: f32,
-    /// the color of the label (left side)
-    pub label_color: String,
-    /// the text color of the label (left side)
-    pub label_text_color: String,
-    /// the shadow color of the label (left side)
-    pub label_shadow_color: String,
-    /// the color of the message (right side)
-    pub msg_color: String,
-    /// the text color of the message (right side)
-    pub msg_text_color: String,
-    /// the shadow color of the message (right side)
-    pub msg_shadow_color: String,
+    /// each segment, from left to right, starting with the label and the message
+    pub segments: Vec<SegmentLayout>,
+}
+
+#[derive(Default, Debug)]
+/// The formatted layout of one segment of a badge.
+pub(crate) struct SegmentLayout {
+    /// normalized text
+    pub text_norm: String,
+    /// whether the text reads right-to-left
+    pub text_rtl: bool,
+    /// width of the text, in px
+    pub text_width: f32,
+    /// text placement in x
+    pub text_x: f32,
+    /// placement in x of the part of the badge width that this segment takes up
+    pub slot_x: f32,
+    /// width of the part of the badge that this segment takes up, outline included, in px
+    pub slot_width: f32,
+    /// placement of the background rectangle in x
+    pub rect_x: f32,
+    /// width of the background rectangle, in px
+    pub rect_width: f32,
+    /// URL link for the segment
+    pub link: String,
+    /// title for the segment
+    pub title: String,
+    /// the background color, empty when it has a gradient
+    pub color: String,
+    /// the background gradient, if it has one
+    pub gradient: Option<Gradient>,
+    /// the text color
+    pub text_color: String,
+    /// the shadow color
+    pub shadow_color: String,
+    /// the background color in dark mode, empty to keep `color`
+    pub dark_color: String,
+    /// the text color in dark mode, empty to keep `text_color`
+    pub dark_text_color: String,
+    /// the shadow color in dark mode, empty to keep `shadow_color`
+    pub dark_shadow_color: String,
+    /// contrast of the text with the background, if there is text
+    pub contrast: Option<SideContrast>,
+}
+
+impl Layout {
+    /// Whether any segment has a color for dark mode.
+    pub fn has_dark_colors(&self) -> bool {
+        self.segments.iter().any(|segment| {
+            !(segment.dark_color.is_empty()
+                && segment.dark_text_color.is_empty()
+                && segment.dark_shadow_color.is_empty())
+        })
+    }
+
+    /// Forget the dark mode colors of every segment.
+    pub fn clear_dark_colors(&mut self) {
+        for segment in self.segments.iter_mut() {
+            segment.dark_color.clear();
+            segment.dark_text_color.clear();
+            segment.dark_shadow_color.clear();
+        }
+    }
 }
 
 /// Error types that may occur on badge generation.
@@ -205,6 +611,10 @@ pub enum BadgeError {
     /// a file system error, not an error with badge generation.
     #[error("Unable to save the badge SVG to {0}.")]
     CannotSaveToFile(String),
+    /// RSBadges is unable to read a file it was given, such as a report. This is usually
+    /// a file system error, like a missing file.
+    #[error("Unable to read {0}.")]
+    CannotReadFile(String),
     /// RSBadges is unable to download the logo specified, and therefore cannot embed the
     /// data into the SVG. This is usually due to a malformed logo URI.
     #[error("Unable to download and embed the logo. Attempted to load from {0}.")]
@@ -220,4 +630,40 @@ pub enum BadgeError {
         - forthebadge\n- social"
     )]
     InvalidStyle(String),
+    /// A generated badge is not well-formed SVG. This is a bug in RSBadges; please
+    /// report it, along with the badge that caused it.
+    #[error("The generated badge is not valid SVG. {0}")]
+    InvalidSvg(String),
+    /// Some text on the badge contrasts too little with its background to meet WCAG
+    /// level AA. This only happens with [ContrastMode::Strict].
+    #[error("The badge text is not readable enough. {0}")]
+    InsufficientContrast(String),
+    /// A [ColorScale](super::ColorScale) can't pick a color, because it is empty or,
+    /// from the command line, can't be parsed.
+    #[error("The color scale is not valid. {0}")]
+    InvalidScale(String),
+    /// A report given to RSBadges, such as a coverage report, is in an unknown format
+    /// or can't be parsed.
+    #[error("The report is not valid. {0}")]
+    InvalidReport(String),
+    /// A crate manifest, such as a Cargo.toml, can't be parsed or lacks the field a
+    /// badge shows.
+    #[error("The crate manifest is not valid. {0}")]
+    InvalidManifest(String),
+    /// A git repository can't be read, because git isn't installed, the path isn't a
+    /// repository, or the repository lacks what a badge shows, such as a tag.
+    #[error("Unable to read the git repository. {0}")]
+    InvalidRepository(String),
+    /// A README doesn't have exactly one badge block, marked by
+    /// [README_START](crate::README_START) and [README_END](crate::README_END).
+    #[error("Unable to find the badge block of the README. {0}")]
+    InvalidReadme(String),
+    /// A README or badge file doesn't match what would be generated now, when only
+    /// checking them.
+    #[error("The badges are out of date. {0}")]
+    OutOfDate(String),
+    /// A suffix given for the IDs of a badge would make invalid IDs. Suffixes may only
+    /// hold ASCII letters, digits, `-` and `_`.
+    #[error("{0} can't be used in the IDs of a badge.")]
+    InvalidIdSuffix(String),
 }
@@ -0,0 +1,153 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Color scales that pick a badge color from a value, such as a coverage percentage
+
+use super::badge_type::BadgeError;
+use super::format_helper::*;
+
+/// How to pick a color from a value, for badges such as
+/// [Badge::percentage](super::Badge::percentage).
+///
+/// Each variant holds `(value, color)` pairs, in any order. Colors accept the same
+/// formats as [label_color](super::Badge::label_color), apart from gradients, and are
+/// returned as hex.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::ColorScale;
+/// let scale = ColorScale::Thresholds(vec![
+///     (0.0, String::from("red")),
+///     (80.0, String::from("green")),
+/// ]);
+/// assert_eq!(scale.color_for(79.9).unwrap(), "#f00");
+/// assert_eq!(scale.color_for(80.0).unwrap(), "#008000");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorScale {
+    /// Use the color of the highest value that is reached. Values below all of them
+    /// get the color of the lowest.
+    Thresholds(Vec<(f32, String)>),
+    /// Blend between the colors of the values on either side. Values outside of the
+    /// scale get the color at its nearest end.
+    Interpolated(Vec<(f32, String)>),
+}
+
+impl ColorScale {
+    /// The coverage colors used by Shields.io: red below 50, then orange, yellow,
+    /// yellow-green, green, and bright green from 90 up.
+    pub fn coverage() -> ColorScale {
+        ColorScale::Thresholds(
+            [
+                (0.0, RED),
+                (50.0, ORANGE),
+                (60.0, YELLOW),
+                (70.0, YELLOW_GREEN),
+                (80.0, GREEN),
+                (90.0, BRIGHT_GREEN),
+            ]
+            .iter()
+            .map(|(value, color)| (*value, String::from(*color)))
+            .collect(),
+        )
+    }
+
+    /// A smooth scale from red at 0, through yellow at 50, to green at 100.
+    pub fn red_yellow_green() -> ColorScale {
+        ColorScale::Interpolated(vec![
+            (0.0, String::from(RED)),
+            (50.0, String::from(YELLOW)),
+            (100.0, String::from(BRIGHT_GREEN)),
+        ])
+    }
+
+    /// The color of `value` on this scale.
+    ///
+    /// # Errors
+    ///
+    /// - [InvalidScale](BadgeError::InvalidScale), if the scale has no colors, or
+    ///   `value` or a value of the scale is not a finite number
+    /// - [ColorNotValid](BadgeError::ColorNotValid), if the picked color, or a color
+    ///   it is interpolated from, can't be parsed
+    ///
+    pub fn color_for(&self, value: f32) -> Result<String, BadgeError> {
+        if !value.is_finite() {
+            return Err(BadgeError::InvalidScale(format!(
+                "{} is not a number on the scale.",
+                value
+            )));
+        }
+        let (ColorScale::Thresholds(points) | ColorScale::Interpolated(points)) = self;
+        if let Some((point, _)) = points.iter().find(|(point, _)| !point.is_finite()) {
+            return Err(BadgeError::InvalidScale(format!(
+                "{} is not a number, so it can't be a value of the scale.",
+                point
+            )));
+        }
+        let mut points: Vec<&(f32, String)> = points.iter().collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return Err(BadgeError::InvalidScale(String::from(
+                "A color scale needs at least one color.",
+            )));
+        };
+
+        match self {
+            ColorScale::Thresholds(_) => {
+                let reached = points
+                    .iter()
+                    .rev()
+                    .find(|(threshold, _)| value >= *threshold);
+                Ok(format_color(&verify_color(&reached.unwrap_or(first).1)?))
+            }
+            ColorScale::Interpolated(_) => {
+                if value <= first.0 {
+                    return Ok(format_color(&verify_color(&first.1)?));
+                }
+                for pair in points.windows(2) {
+                    let ((low, low_color), (high, high_color)) = (pair[0], pair[1]);
+                    if value <= *high {
+                        let (a, b) = (verify_color(low_color)?, verify_color(high_color)?);
+                        let amount = (value - low) / (high - low);
+                        let mix = |x: f32, y: f32| x + (y - x) * amount;
+                        let mut color = a;
+                        color.red = mix(a.red, b.red);
+                        color.green = mix(a.green, b.green);
+                        color.blue = mix(a.blue, b.blue);
+                        color.alpha = mix(a.alpha, b.alpha);
+                        return Ok(format_color(&color));
+                    }
+                }
+                Ok(format_color(&verify_color(&last.1)?))
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Badges as data URIs, to inline them in HTML or Markdown without an image file
+
+use super::badge_grid::BadgeGrid;
+use super::badge_type::BadgeError;
+use super::style::Style;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// How the SVG is written into a data URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataUriEncoding {
+    /// `data:image/svg+xml;base64,...`, which works everywhere.
+    Base64,
+    /// `data:image/svg+xml,...`, with the SVG percent-encoded. This is shorter than
+    /// base64 for most badges, and stays readable.
+    UrlEncoded,
+}
+
+/// Write an SVG as a data URI.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::{svg_data_uri, DataUriEncoding};
+/// let svg = "<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+/// assert_eq!(
+///     svg_data_uri(svg, DataUriEncoding::UrlEncoded),
+///     "data:image/svg+xml,%3Csvg%20xmlns=%22http://www.w3.org/2000/svg%22/%3E"
+/// );
+/// ```
+///
+pub fn svg_data_uri(svg: &str, encoding: DataUriEncoding) -> String {
+    match encoding {
+        DataUriEncoding::Base64 => {
+            format!(
+                "data:image/svg+xml;base64,{}",
+                STANDARD.encode(svg.as_bytes())
+            )
+        }
+        DataUriEncoding::UrlEncoded => format!("data:image/svg+xml,{}", percent_encode(svg)),
+    }
+}
+
+/// Percent-encode everything but the characters that are safe in a URL, in an HTML
+/// attribute, and in a Markdown link.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'$'
+            | b'*'
+            | b'+'
+            | b','
+            | b'/'
+            | b':'
+            | b';'
+            | b'='
+            | b'?'
+            | b'@' => encoded.push(byte as char),
+            _ => encoded += &format!("%{:02X}", byte),
+        }
+    }
+    encoded
+}
+
+impl Style {
+    /// Generates the SVG of the badge, as with [generate_svg](Style::generate_svg), and
+    /// writes it as a data URI, for an `<img src>` or a Markdown image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, DataUriEncoding, Style};
+    /// let uri = Style::Flat(Badge::default())
+    ///     .generate_data_uri(DataUriEncoding::Base64)
+    ///     .unwrap();
+    /// assert!(uri.starts_with("data:image/svg+xml;base64,"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [generate_svg](Style::generate_svg).
+    ///
+    pub fn generate_data_uri(&self, encoding: DataUriEncoding) -> Result<String, BadgeError> {
+        Ok(svg_data_uri(&self.generate_svg()?, encoding))
+    }
+}
+
+impl BadgeGrid {
+    /// Generates the SVG of the grid, as with [generate_svg](BadgeGrid::generate_svg),
+    /// and writes it as a data URI.
+    ///
+    /// # Errors
+    ///
+    /// The same as [generate_svg](Style::generate_svg), for any of the badges.
+    ///
+    pub fn generate_data_uri(&self, encoding: DataUriEncoding) -> Result<String, BadgeError> {
+        Ok(svg_data_uri(&self.generate_svg()?, encoding))
+    }
+}
@@ -30,11 +30,16 @@
 //! Different helper functions used when formatting a badge for SVG generation.
 
 use super::badge_type::BadgeError;
-use base64::{engine::general_purpose::STANDARD, Engine as _};
+use super::data_uri::{svg_data_uri, DataUriEncoding};
+#[cfg(feature = "shaping")]
+use super::text_shaping;
 use css_color::Rgba;
+#[cfg(not(feature = "shaping"))]
 use rusttype::{point, Font, Scale};
 use std::path::Path;
+use std::sync::OnceLock;
 use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 use ureq::{
     config::Config,
     tls::{TlsConfig, TlsProvider},
@@ -47,16 +52,183 @@ const DARK_TEXT_COLOR: &str = "#333";
 const LIGHT_SHADOW_COLOR: &str = "#ccc";
 const DARK_SHADOW_COLOR: &str = "#010101";
 
+// Status colors, from the Shields.io palette
+pub const BRIGHT_GREEN: &str = "#4c1";
+pub const GREEN: &str = "#97ca00";
+pub const YELLOW_GREEN: &str = "#a4a61d";
+pub const YELLOW: &str = "#dfb317";
+pub const ORANGE: &str = "#fe7d37";
+pub const RED: &str = "#e05d44";
+
 // Gamma-adjusted greyscale midpoint normalized to the 0-1 range
 const BRIGHTNESS_THRESHOLD: f32 = 0.579;
 
+#[derive(Debug, Clone, Copy)]
 pub struct AccentColors {
     pub text_color: &'static str,
     pub shadow_color: &'static str,
 }
 
+/// First character covered by the precomputed width tables (space).
+const WIDTH_TABLE_START: char = ' ';
+/// Number of characters covered by the precomputed width tables (printable ASCII).
+const WIDTH_TABLE_LEN: usize = 95;
+
+/// The fonts bundled with RSBadges, used to measure badge text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeFont {
+    /// PT Sans Regular, used by most badge styles
+    PtSansRegular,
+    /// PT Sans Bold, used for the message of "for the badge" badges
+    PtSansBold,
+    /// Open Sans Regular, used by social badges
+    OpenSansRegular,
+}
+
+/// A parsed font along with its advance-width table for printable ASCII.
+pub struct CachedFont {
+    /// The parsed font, used for any text outside of the width table
+    #[cfg(not(feature = "shaping"))]
+    pub font: Font<'static>,
+    /// The font prepared for shaping, used for any text outside of the width table
+    #[cfg(feature = "shaping")]
+    pub face: rustybuzz::Face<'static>,
+    /// Advance width of each printable ASCII character at a font size of 1px
+    pub ascii_widths: [f32; WIDTH_TABLE_LEN],
+    /// Kerning adjustment between each pair of printable ASCII characters at a font
+    /// size of 1px. Each row holds every pair starting with one character, and is
+    /// filled in the first time that character is measured.
+    #[cfg(feature = "shaping")]
+    pub ascii_kerning: Vec<OnceLock<[f32; WIDTH_TABLE_LEN]>>,
+}
+
+impl BadgeFont {
+    /// The raw font file for this font.
+    fn bytes(self) -> &'static [u8] {
+        match self {
+            BadgeFont::PtSansRegular => include_bytes!("PTSans-Regular.ttf"),
+            BadgeFont::PtSansBold => include_bytes!("PTSans-Bold.ttf"),
+            BadgeFont::OpenSansRegular => include_bytes!("OpenSans-Regular.ttf"),
+        }
+    }
+
+    /// The process-wide cache slot for this font.
+    fn cache(self) -> &'static OnceLock<Option<CachedFont>> {
+        static PT_SANS_REGULAR: OnceLock<Option<CachedFont>> = OnceLock::new();
+        static PT_SANS_BOLD: OnceLock<Option<CachedFont>> = OnceLock::new();
+        static OPEN_SANS_REGULAR: OnceLock<Option<CachedFont>> = OnceLock::new();
+        match self {
+            BadgeFont::PtSansRegular => &PT_SANS_REGULAR,
+            BadgeFont::PtSansBold => &PT_SANS_BOLD,
+            BadgeFont::OpenSansRegular => &OPEN_SANS_REGULAR,
+        }
+    }
+
+    /// Get this font, parsing it and building its width table on first use.
+    pub fn load(self) -> Result<&'static CachedFont, BadgeError> {
+        self.cache()
+            .get_or_init(|| CachedFont::new(self.bytes()))
+            .as_ref()
+            .ok_or(BadgeError::CannotLoadFont)
+    }
+}
+
+/// The printable ASCII character at this position in a width table.
+fn table_char(index: usize) -> char {
+    char::from(WIDTH_TABLE_START as u8 + index as u8)
+}
+
+impl CachedFont {
+    /// Parse a font and precompute the advance widths of every printable ASCII character.
+    #[cfg(not(feature = "shaping"))]
+    fn new(bytes: &'static [u8]) -> Option<CachedFont> {
+        let font = load_font(bytes).ok()?;
+        let scale = Scale::uniform(1.0);
+        let mut ascii_widths = [0.0; WIDTH_TABLE_LEN];
+        for (i, width) in ascii_widths.iter_mut().enumerate() {
+            *width = font
+                .glyph(table_char(i))
+                .scaled(scale)
+                .h_metrics()
+                .advance_width;
+        }
+        Some(CachedFont { font, ascii_widths })
+    }
+
+    /// Parse a font and precompute the advance widths of every printable ASCII character.
+    #[cfg(feature = "shaping")]
+    fn new(bytes: &'static [u8]) -> Option<CachedFont> {
+        let face = rustybuzz::Face::from_slice(bytes, 0)?;
+        let mut ascii_widths = [0.0; WIDTH_TABLE_LEN];
+        for (i, width) in ascii_widths.iter_mut().enumerate() {
+            *width = text_shaping::face_width(&face, &table_char(i).to_string(), 1.0);
+        }
+        let ascii_kerning = (0..WIDTH_TABLE_LEN).map(|_| OnceLock::new()).collect();
+        Some(CachedFont {
+            face,
+            ascii_widths,
+            ascii_kerning,
+        })
+    }
+
+    /// Get the kerning between one character and every other in the width table.
+    ///
+    /// Kerning is found by shaping each pair of characters and comparing the result
+    /// to the sum of their individual advances.
+    #[cfg(feature = "shaping")]
+    fn kerning_row(&self, first: usize) -> &[f32; WIDTH_TABLE_LEN] {
+        self.ascii_kerning[first].get_or_init(|| {
+            let mut row = [0.0; WIDTH_TABLE_LEN];
+            let mut pair = String::with_capacity(2);
+            for (second, kerning) in row.iter_mut().enumerate() {
+                pair.clear();
+                pair.push(table_char(first));
+                pair.push(table_char(second));
+                *kerning = text_shaping::face_width(&self.face, &pair, 1.0)
+                    - self.ascii_widths[first]
+                    - self.ascii_widths[second];
+            }
+            row
+        })
+    }
+
+    /// Position of a character in the width table, if it is in the table.
+    fn table_index(c: char) -> Option<usize> {
+        (c as usize)
+            .checked_sub(WIDTH_TABLE_START as usize)
+            .filter(|i| *i < WIDTH_TABLE_LEN)
+    }
+
+    /// Measure text at a 1px font size using only the width tables. Returns `None`
+    /// if any character is not in the tables.
+    fn table_width(&self, text: &str) -> Option<f32> {
+        let indices = text
+            .chars()
+            .map(CachedFont::table_index)
+            .collect::<Option<Vec<_>>>()?;
+        let advance = indices.iter().map(|i| self.ascii_widths[*i]).sum::<f32>();
+        Some(advance + self.table_kerning(&indices))
+    }
+
+    /// Sum the kerning between each neighboring pair of width table entries.
+    #[cfg(feature = "shaping")]
+    fn table_kerning(&self, indices: &[usize]) -> f32 {
+        indices
+            .windows(2)
+            .map(|pair| self.kerning_row(pair[0])[pair[1]])
+            .sum()
+    }
+
+    /// Without shaping, kerning is not taken into account.
+    #[cfg(not(feature = "shaping"))]
+    fn table_kerning(&self, _indices: &[usize]) -> f32 {
+        0.0
+    }
+}
+
 /// Load a font into Rust.
 /// Docs: https://gitlab.redox-os.org/redox-os/rusttype/-/blob/master/dev/examples/ascii.rs
+#[cfg(not(feature = "shaping"))]
 pub fn load_font<'a>(bytes: &'static [u8]) -> Result<Font<'a>, BadgeError> {
     match Font::try_from_bytes(bytes) {
         Some(f) => Ok(f),
@@ -66,17 +238,58 @@ pub fn load_font<'a>(bytes: &'static [u8]) -> Result<Font<'a>, BadgeError> {
 
 /// Produce the text dimensions given the font, text, and requested size of the
 /// string.
-pub fn get_text_dims(font: &Font, text: &str, font_size: f32) -> (String, f32) {
+///
+/// Text made up entirely of printable ASCII is measured from the font's precomputed
+/// width and kerning tables. With the `shaping` feature, anything else is shaped, with
+/// system fonts filling in for characters the bundled font lacks; without it, anything
+/// else is laid out glyph by glyph.
+pub fn get_text_dims(font: &CachedFont, text: &str, font_size: f32) -> (String, f32) {
     let norm_text = text.nfc().collect::<String>();
+    let glyphs_width = match font.table_width(&norm_text) {
+        Some(width) => width * font_size,
+        None => untabled_width(font, &norm_text, font_size),
+    };
+    (norm_text, glyphs_width)
+}
+
+/// Measure text that isn't covered by the width tables by shaping it.
+#[cfg(feature = "shaping")]
+fn untabled_width(font: &CachedFont, text: &str, font_size: f32) -> f32 {
+    text_shaping::shaped_width(&font.face, text, font_size)
+}
+
+/// Measure text that isn't covered by the width tables glyph by glyph.
+#[cfg(not(feature = "shaping"))]
+fn untabled_width(font: &CachedFont, text: &str, font_size: f32) -> f32 {
     let scale = Scale::uniform(font_size);
-    let layout = font.layout(&norm_text, scale, point(0.0, 0.0));
-    let mut glyphs_width = layout.fold(0.0, |acc, x| {
+    let layout = font.font.layout(text, scale, point(0.0, 0.0));
+    layout.fold(0.0, |acc, x| {
         acc + x.into_unpositioned().h_metrics().advance_width
-    });
-    if glyphs_width as usize % 2 == 0 {
-        glyphs_width += 1.0;
+    })
+}
+
+/// The extra width that letter spacing adds to text. Spacing goes between each pair
+/// of graphemes, so that accented and combined characters count once, no matter
+/// how many bytes or code points they take up.
+pub fn letter_spacing_width(text: &str, letter_spacing: f32) -> f32 {
+    let graphemes = text.graphemes(true).count();
+    graphemes.saturating_sub(1) as f32 * letter_spacing
+}
+
+/// Check if text should be laid out right-to-left, based on its first strongly
+/// directional character.
+pub fn is_rtl(text: &str) -> bool {
+    unicode_bidi::get_base_direction(text) == unicode_bidi::Direction::Rtl
+}
+
+/// The color of a status that is either passing or failing, such as a test run.
+#[cfg(feature = "reports")]
+pub fn status_color(passing: bool) -> &'static str {
+    if passing {
+        BRIGHT_GREEN
+    } else {
+        RED
     }
-    (norm_text, glyphs_width)
 }
 
 /// Verify that the string passed in is a valid color.
@@ -87,12 +300,39 @@ pub fn verify_color(color: &str) -> Result<Rgba, BadgeError> {
     }
 }
 
+/// Convert a color channel from the 0-1 range to a byte.
+fn channel_byte(channel: f32) -> u8 {
+    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Format a color for the SVG: compact hex when it is opaque, `rgba()` otherwise.
 pub fn format_color(color: &Rgba) -> String {
-    format!(
-        "rgb({}, {}, {})",
-        color.red * 255.0,
-        color.green * 255.0,
-        color.blue * 255.0
+    let [red, green, blue] = [color.red, color.green, color.blue].map(channel_byte);
+    let alpha = (color.alpha.clamp(0.0, 1.0) * 1000.0).round() / 1000.0;
+    if alpha < 1.0 {
+        format!("rgba({}, {}, {}, {})", red, green, blue, alpha)
+    } else if [red, green, blue].iter().all(|c| c % 17 == 0) {
+        format!("#{:x}{:x}{:x}", red / 17, green / 17, blue / 17)
+    } else {
+        format!("#{:02x}{:02x}{:02x}", red, green, blue)
+    }
+}
+
+/// The opaque color seen when `color` is drawn over `background`, accounting for the
+/// transparency of both. Anything left transparent shows the white of the page.
+pub fn blend_over(color: &Rgba, background: &Rgba) -> Rgba {
+    let white = Rgba::new(1.0, 1.0, 1.0, 1.0);
+    let background = if background.alpha < 1.0 {
+        blend_over(background, &white)
+    } else {
+        *background
+    };
+    let blend = |c: f32, b: f32| c * color.alpha + b * (1.0 - color.alpha);
+    Rgba::new(
+        blend(color.red, background.red),
+        blend(color.green, background.green),
+        blend(color.blue, background.blue),
+        1.0,
     )
 }
 
@@ -104,13 +344,67 @@ pub fn rgb_to_xyz(color_item: f32) -> f32 {
     }
 }
 
+/// Relative luminance of a color, as defined by WCAG 2.x: 0 for black, 1 for white.
+pub fn relative_luminance(color: &Rgba) -> f32 {
+    rgb_to_xyz(color.red) * 0.2126
+        + rgb_to_xyz(color.green) * 0.7152
+        + rgb_to_xyz(color.blue) * 0.0722
+}
+
+/// WCAG 2.x contrast ratio between two colors, from 1 (no contrast) to 21 (black on white).
+pub fn contrast_ratio(a: &Rgba, b: &Rgba) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Mix `color` with `target` by `amount`, from 0 (all `color`) to 1 (all `target`).
+fn mix_colors(color: &Rgba, target: &Rgba, amount: f32) -> Rgba {
+    let mix = |a: f32, b: f32| a + (b - a) * amount;
+    Rgba::new(
+        mix(color.red, target.red),
+        mix(color.green, target.green),
+        mix(color.blue, target.blue),
+        color.alpha,
+    )
+}
+
+/// Darken or lighten `color` as little as possible for it to reach `min_ratio` contrast
+/// with `other`. Returns None if even black or white can't reach it.
+pub fn adjust_for_contrast(color: &Rgba, other: &Rgba, min_ratio: f32) -> Option<Rgba> {
+    if contrast_ratio(color, other) >= min_ratio {
+        return Some(*color);
+    }
+    // Move toward whichever of black and white contrasts more with the other color
+    let black = Rgba::new(0.0, 0.0, 0.0, color.alpha);
+    let white = Rgba::new(1.0, 1.0, 1.0, color.alpha);
+    let target = if contrast_ratio(&black, other) >= contrast_ratio(&white, other) {
+        black
+    } else {
+        white
+    };
+    if contrast_ratio(&target, other) < min_ratio {
+        return None;
+    }
+
+    // Contrast grows steadily as the color moves toward the target, so binary search
+    // for the smallest change that passes
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..16 {
+        let amount = 0.5 * (low + high);
+        if contrast_ratio(&mix_colors(color, &target, amount), other) >= min_ratio {
+            high = amount;
+        } else {
+            low = amount;
+        }
+    }
+    Some(mix_colors(color, &target, high))
+}
+
 // From https://stackoverflow.com/a/75110271/2027612
 // which uses the method from this answer: https://stackoverflow.com/a/3943023/2027612 but applies it
 // using the CIE XYZ color space which is a better model for how the eyes perceive colors
-pub fn get_accent_colors(background_color: &Rgba) -> AccentColors {
-    let brightness = rgb_to_xyz(background_color.red) * 0.2126
-        + rgb_to_xyz(background_color.green) * 0.7152
-        + rgb_to_xyz(background_color.blue) * 0.0722;
+/// Pick light or dark accent colors for a background with the given relative luminance.
+pub fn get_accent_colors(brightness: f32) -> AccentColors {
     // Check if the background color requires light or dark text depending on the brightness of the color
     if brightness <= BRIGHTNESS_THRESHOLD {
         AccentColors {
@@ -165,12 +459,9 @@ pub fn attempt_logo_download(logo_uri: &str) -> Result<String, BadgeError> {
 
     let data = match std::fs::read_to_string(local_path) {
         Ok(f) => f,
+        // If not local, download
         Err(_) => create_embedded_logo(logo_uri)?,
     };
 
-    // If not local, download
-    Ok(format!(
-        "data:image/svg+xml;base64,{}",
-        STANDARD.encode(data.as_bytes())
-    ))
+    Ok(svg_data_uri(&data, DataUriEncoding::Base64))
 }
@@ -32,326 +32,372 @@
 
 use super::badge_type::*;
 use super::format_helper::*;
-/// Generate the correct layout information for a Plastic badge
-pub(crate) fn plastic(badge: &Badge) -> Result<Layout, BadgeError> {
-    let mut layout = Layout::default();
-
-    // Normalize text
-    let font = load_font(include_bytes!("PTSans-Regular.ttf"))?;
-    let (label_text_norm, label_text_width) = get_text_dims(&font, &badge.label_text, 17.0);
-    layout.label_text_norm = label_text_norm;
-    layout.label_text_width = label_text_width;
-    let (msg_text_norm, msg_text_width) = get_text_dims(&font, &badge.msg_text, 17.0);
-    layout.msg_text_norm = msg_text_norm;
-    layout.msg_text_width = msg_text_width;
-
-    // Padding and spacing calculations
-    let horiz_padding = 5.0;
-    layout.badge_height = 18.0;
-
-    // Logo padding and width
-    let mut total_logo_width = 0.0;
-    layout.logo_width = 0.0;
-    layout.logo_padding = 0.0;
-    if !badge.logo.is_empty() {
-        if !badge.label_text.is_empty() {
-            layout.logo_padding = 3.0;
-        }
-        let logo_height = 14.0;
-        layout.logo_y = (layout.badge_height - logo_height) * 0.5;
-        layout.logo_x = horiz_padding;
-        layout.logo_width = 14.0;
-        total_logo_width = layout.logo_width + layout.logo_padding;
-    }
-
-    // Label padding and width
-    let label_text_margin = total_logo_width + 1.0;
-    layout.label_total_width = 0.0;
-    if !badge.label_text.is_empty() {
-        layout.label_total_width =
-            layout.label_text_width + (2.0 * horiz_padding) + total_logo_width;
-    }
-    layout.label_text_x = label_text_margin + (0.5 * layout.label_text_width) + horiz_padding;
+use super::gradient::Fill;
+use super::style_metrics::{SideMetrics, StyleMetrics};
+use css_color::Rgba;
+
+/// Normalize and measure the text for one side of a badge. Returns the normalized
+/// text and its width, letter spacing included.
+fn measure_text(text: &str, metrics: &SideMetrics) -> Result<(String, f32), BadgeError> {
+    let font = metrics.font.load()?;
+    let (text_norm, text_width) =
+        get_text_dims(font, &metrics.text_transform.apply(text), metrics.font_size);
+    let spacing = letter_spacing_width(&text_norm, metrics.letter_spacing);
+    Ok((text_norm, text_width + spacing))
+}
 
-    // Message padding and width
-    let mut msg_text_margin = layout.label_total_width;
-    if !badge.msg_text.is_empty() {
-        msg_text_margin -= 1.0;
+/// The accessible name of a badge: the user's override if there is one, otherwise
+/// the badge content in the form "label: message", with any extra segments after
+/// the message, separated by commas.
+pub(super) fn accessible_name(badge: &Badge) -> String {
+    if !badge.aria_label.is_empty() {
+        return badge.aria_label.clone();
     }
-
-    if badge.label_text.is_empty() {
-        if !badge.logo.is_empty() {
-            msg_text_margin += layout.logo_width + horiz_padding;
-        } else {
-            msg_text_margin += 1.0;
-        }
+    let content = [&badge.msg_text]
+        .into_iter()
+        .chain(badge.extra_segments.iter().map(|segment| &segment.text))
+        .filter(|text| !text.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    match (badge.label_text.is_empty(), content.is_empty()) {
+        (false, false) => format!("{}: {}", badge.label_text, content),
+        (false, true) => badge.label_text.clone(),
+        (true, _) => content,
     }
+}
 
-    layout.msg_total_width = layout.msg_text_width + (2.0 * horiz_padding);
-    if !badge.logo.is_empty() && badge.label_text.is_empty() {
-        layout.msg_total_width += layout.logo_width + horiz_padding - 1.0;
+/// The user's color if one was given, otherwise the automatic `default` color.
+fn color_or(color: &str, default: &str) -> Result<String, BadgeError> {
+    if color.is_empty() {
+        Ok(default.to_string())
+    } else {
+        Ok(format_color(&verify_color(color)?))
     }
-
-    layout.msg_text_x = msg_text_margin + (0.5 * layout.msg_text_width) + horiz_padding;
-
-    // Scale back up for the SVG
-    layout.label_text_width *= 10.0;
-    layout.msg_text_width *= 10.0;
-    layout.label_text_x *= 10.0;
-    layout.msg_text_x *= 10.0;
-
-    // Color conversion to string
-    let label_color = verify_color(&badge.label_color)?;
-    let msg_color = verify_color(&badge.msg_color)?;
-    let label_accent_colors = get_accent_colors(&label_color);
-    let msg_accent_colors = get_accent_colors(&msg_color);
-
-    layout.label_color = format_color(&label_color);
-    layout.msg_color = format_color(&msg_color);
-    layout.label_text_color = label_accent_colors.text_color.to_string();
-    layout.msg_text_color = msg_accent_colors.text_color.to_string();
-    layout.label_shadow_color = label_accent_colors.shadow_color.to_string();
-    layout.msg_shadow_color = msg_accent_colors.shadow_color.to_string();
-
-    Ok(layout)
 }
 
-/// Generate the correct layout information for a Flat or Square badge
-pub(crate) fn flat_or_square(badge: &Badge) -> Result<Layout, BadgeError> {
-    let mut layout = Layout::default();
-
-    // Normalize text
-    let font = load_font(include_bytes!("PTSans-Regular.ttf"))?;
-    let (label_text_norm, label_text_width) = get_text_dims(&font, &badge.label_text, 17.0);
-    layout.label_text_norm = label_text_norm;
-    layout.label_text_width = label_text_width;
-    let (msg_text_norm, msg_text_width) = get_text_dims(&font, &badge.msg_text, 17.0);
-    layout.msg_text_norm = msg_text_norm;
-    layout.msg_text_width = msg_text_width;
-
-    // Padding and spacing calculations
-    let horiz_padding = 5.0;
-    layout.badge_height = 20.0;
-
-    // Logo padding and width
-    let mut total_logo_width = 0.0;
-    layout.logo_width = 0.0;
-    layout.logo_padding = 0.0;
-    if !badge.logo.is_empty() {
-        if !badge.label_text.is_empty() {
-            layout.logo_padding = 3.0;
+/// Measure the contrast of one side of a badge, with translucent colors blended over
+/// `page_color`. With [ContrastMode::AutoAdjust], the background fill (or the text
+/// color, when the style draws its own `fixed_background`) is changed first, if needed,
+/// to meet WCAG level AA. Adjusted colors are opaque.
+///
+/// A gradient is measured at the point with the least contrast, and each of its
+/// colors is adjusted separately.
+fn side_contrast(
+    mode: ContrastMode,
+    page_color: &Rgba,
+    fixed_background: Option<&str>,
+    fill: &mut Fill,
+    text_color: &mut String,
+) -> Result<SideContrast, BadgeError> {
+    let mut text = verify_color(text_color)?;
+    let fixed_background = fixed_background.map(verify_color).transpose()?;
+
+    if mode == ContrastMode::AutoAdjust {
+        match fixed_background {
+            Some(fixed) => {
+                let fixed = blend_over(&fixed, page_color);
+                let blended = blend_over(&text, &fixed);
+                if let Some(adjusted) =
+                    adjust_for_contrast(&blended, &fixed, SideContrast::AA_RATIO)
+                {
+                    text = adjusted;
+                    *text_color = format_color(&text);
+                }
+            }
+            None => fill.map_colors(|color| {
+                let blended = blend_over(color, page_color);
+                let text = blend_over(&text, &blended);
+                adjust_for_contrast(&blended, &text, SideContrast::AA_RATIO).unwrap_or(*color)
+            }),
         }
-        let logo_height = 14.0;
-        layout.logo_y = (layout.badge_height - logo_height) * 0.5;
-        layout.logo_x = horiz_padding;
-        layout.logo_width = 14.0;
-        total_logo_width = layout.logo_width + layout.logo_padding;
     }
 
-    // Label padding and width
-    let label_text_margin = total_logo_width + 1.0;
-    layout.label_total_width = 0.0;
-    if !badge.label_text.is_empty() {
-        layout.label_total_width =
-            layout.label_text_width + (2.0 * horiz_padding) + total_logo_width;
-    }
-    layout.label_text_x = label_text_margin + (0.5 * layout.label_text_width) + horiz_padding;
+    let background = fixed_background.map_or_else(|| fill.clone(), Fill::Solid);
+    let (worst, ratio) = background
+        .samples()
+        .into_iter()
+        .map(|color| {
+            let blended = blend_over(&color, page_color);
+            (
+                color,
+                contrast_ratio(&blended, &blend_over(&text, &blended)),
+            )
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap();
+
+    Ok(SideContrast {
+        background: format_color(&worst),
+        text: text_color.clone(),
+        ratio,
+    })
+}
 
-    // Message padding and width
-    let mut msg_text_margin = layout.label_total_width;
-    if !badge.msg_text.is_empty() {
-        msg_text_margin -= 1.0;
+/// The dark mode background, text, and shadow colors of one side of a badge. When
+/// the background is set, the text and shadow colors are picked from it as it appears
+/// on a black page, unless the text color is set too. Empty colors stay empty.
+fn dark_side_colors(
+    background: &str,
+    text_color: &str,
+) -> Result<(String, String, String), BadgeError> {
+    if background.is_empty() {
+        return Ok((String::new(), color_or(text_color, "")?, String::new()));
     }
+    let background = verify_color(background)?;
+    let black = Rgba::new(0.0, 0.0, 0.0, 1.0);
+    let accent_colors = get_accent_colors(relative_luminance(&blend_over(&background, &black)));
+    Ok((
+        format_color(&background),
+        color_or(text_color, accent_colors.text_color)?,
+        String::from(accent_colors.shadow_color),
+    ))
+}
 
-    if badge.label_text.is_empty() {
-        if !badge.logo.is_empty() {
-            msg_text_margin += layout.logo_width + horiz_padding;
-        } else {
-            msg_text_margin += 1.0;
+/// Fail with [BadgeError::InsufficientContrast] if any text in the layout is below
+/// WCAG level AA.
+pub(crate) fn check_contrast(layout: &Layout) -> Result<(), BadgeError> {
+    for (i, segment) in layout.segments.iter().enumerate() {
+        if let Some(contrast) = segment.contrast.as_ref().filter(|c| !c.passes_aa()) {
+            let side = match i {
+                0 => String::from("label"),
+                1 => String::from("message"),
+                _ => format!("segment {}", i + 1),
+            };
+            return Err(BadgeError::InsufficientContrast(format!(
+                "The {} text {} on {} has a contrast ratio of {:.2}:1, below the {}:1 \
+                required by WCAG level AA.",
+                side,
+                contrast.text,
+                contrast.background,
+                contrast.ratio,
+                SideContrast::AA_RATIO
+            )));
         }
     }
-
-    layout.msg_total_width = layout.msg_text_width + (2.0 * horiz_padding);
-    if !badge.logo.is_empty() && badge.label_text.is_empty() {
-        layout.msg_total_width += layout.logo_width + horiz_padding - 1.0;
-    }
-
-    layout.msg_text_x = msg_text_margin + (0.5 * layout.msg_text_width) + horiz_padding;
-
-    // Scale back up for the SVG
-    layout.label_text_width *= 10.0;
-    layout.msg_text_width *= 10.0;
-    layout.label_text_x *= 10.0;
-    layout.msg_text_x *= 10.0;
-
-    // Color conversion to string
-    let label_color = verify_color(&badge.label_color)?;
-    let msg_color = verify_color(&badge.msg_color)?;
-    let label_accent_colors = get_accent_colors(&label_color);
-    let msg_accent_colors = get_accent_colors(&msg_color);
-
-    layout.label_color = format_color(&label_color);
-    layout.msg_color = format_color(&msg_color);
-    layout.label_text_color = label_accent_colors.text_color.to_string();
-    layout.msg_text_color = msg_accent_colors.text_color.to_string();
-    layout.label_shadow_color = label_accent_colors.shadow_color.to_string();
-    layout.msg_shadow_color = msg_accent_colors.shadow_color.to_string();
-    Ok(layout)
+    Ok(())
 }
 
-/// Generate the correct layout information for a "For the Badge" badge
-pub(crate) fn for_the_badge(badge: &Badge) -> Result<Layout, BadgeError> {
+/// Generate the layout information for a badge in the style described by `metrics`.
+pub(crate) fn layout(badge: &Badge, metrics: &StyleMetrics) -> Result<Layout, BadgeError> {
     let mut layout = Layout::default();
-
-    // Normalize text
-    let font = load_font(include_bytes!("PTSans-Regular.ttf"))?;
-    let (label_text_norm, label_text_width) =
-        get_text_dims(&font, &badge.label_text.to_uppercase(), 14.0);
-    layout.label_text_norm = label_text_norm;
-    layout.label_text_width = label_text_width;
-    let bold_font = load_font(include_bytes!("PTSans-Bold.ttf"))?;
-    let (msg_text_norm, msg_text_width) =
-        get_text_dims(&bold_font, &badge.msg_text.to_uppercase(), 17.0);
-    layout.msg_text_norm = msg_text_norm;
-    layout.msg_text_width = msg_text_width;
+    let segments = badge.segments();
+    let has_label = !badge.label_text.is_empty();
+    let has_logo = !badge.logo.is_empty();
+
+    // Accessible name and title
+    layout.aria_label = accessible_name(badge);
+    layout.badge_title = if badge.badge_title.is_empty() {
+        layout.aria_label.clone()
+    } else {
+        badge.badge_title.clone()
+    };
 
     // Padding and spacing calculations
-    let horiz_padding = 9.0;
-    layout.badge_height = 28.0;
+    let label_padding = metrics.label.horiz_padding;
+    let msg_padding = metrics.msg.horiz_padding;
+    layout.badge_height = metrics.badge_height;
+    layout.text_y = metrics.text_y;
+    layout.outline_width = metrics.outline_width;
 
     // Logo padding and width
     let mut total_logo_width = 0.0;
-    layout.logo_width = 0.0;
-    layout.logo_padding = 0.0;
-    if !badge.logo.is_empty() {
-        if !badge.label_text.is_empty() || badge.use_logo_as_label {
-            layout.logo_padding = 3.0;
+    if has_logo {
+        if has_label {
+            layout.logo_padding = metrics.logo_padding;
         }
-        let logo_height = 14.0;
-        layout.logo_y = (layout.badge_height - logo_height) * 0.5;
-        layout.logo_x = horiz_padding;
-        layout.logo_width = 14.0;
+        layout.logo_y = (layout.badge_height - metrics.logo_size) * 0.5;
+        layout.logo_x = label_padding;
+        layout.logo_width = metrics.logo_size;
         total_logo_width = layout.logo_width + layout.logo_padding;
     }
 
-    // Label padding and width
-    // This logic is something else, lemme tell ya
-    layout.label_total_width = layout.label_text_width + total_logo_width + 10.0;
-    if !badge.label_text.is_empty() {
-        layout.label_total_width += 10.0 + (2.0 * layout.label_text_norm.len() as f32)
-    } else if !badge.logo.is_empty() {
-        if !badge.label_text.is_empty() || badge.use_logo_as_label {
-            layout.label_total_width += 7.0;
+    // Lay out each segment in turn, from left to right. The label is first, and every
+    // segment after it is measured and padded like the message.
+    let mut rect_end = 0.0;
+    let mut slot_x = 0.0;
+    for (i, segment) in segments.iter().enumerate() {
+        let side_metrics = if i == 0 { &metrics.label } else { &metrics.msg };
+        let (text_norm, text_width) = measure_text(&segment.text, side_metrics)?;
+        let mut segment_layout = SegmentLayout {
+            text_rtl: is_rtl(&text_norm),
+            text_norm,
+            text_width,
+            slot_x,
+            link: segment.link.clone(),
+            title: segment.title.clone(),
+            ..SegmentLayout::default()
+        };
+
+        if i == 0 {
+            // Label padding and width
+            if has_label || metrics.keep_empty_label {
+                segment_layout.rect_width = text_width + total_logo_width + (2.0 * label_padding);
+            } else if has_logo {
+                // A lone logo gets padding on both sides when it acts as the label.
+                // Otherwise, it sits on the message background and shares its padding.
+                segment_layout.rect_width = layout.logo_width + label_padding;
+                if badge.use_logo_as_label {
+                    segment_layout.rect_width += label_padding;
+                }
+            }
+            segment_layout.rect_x = 0.5 * metrics.outline_width;
+            segment_layout.text_x =
+                total_logo_width + label_padding + (0.5 * text_width) + metrics.text_nudge;
+            segment_layout.slot_width = segment_layout.rect_width + metrics.outline_width;
+            rect_end = segment_layout.rect_width;
         } else {
-            layout.label_total_width -= 7.0;
+            // Message padding and width, for the message and every segment after it
+            let x = rect_end + metrics.gutter;
+            segment_layout.rect_width = text_width + (2.0 * msg_padding);
+            segment_layout.rect_x = x + (0.5 * metrics.outline_width);
+            segment_layout.text_x = x + (0.5 * segment_layout.rect_width);
+            if has_label || i > 1 {
+                segment_layout.text_x -= metrics.text_nudge;
+            }
+            if metrics.hide_empty_msg && segment_layout.text_norm.is_empty() {
+                segment_layout.rect_width = 0.0;
+            } else {
+                segment_layout.slot_width =
+                    x + segment_layout.rect_width + metrics.outline_width - slot_x;
+                rect_end = x + segment_layout.rect_width;
+            }
         }
-    } else {
-        layout.label_total_width -= 11.0;
+        slot_x += segment_layout.slot_width;
+        layout.segments.push(segment_layout);
     }
 
-    layout.label_text_x = (layout.label_total_width + total_logo_width) / 2.0;
-    layout.label_text_width = layout.label_total_width - (24.0 + total_logo_width);
-
-    // Message padding and width
-    layout.msg_total_width =
-        layout.msg_text_width + 20.0 + (1.5 * layout.msg_text_norm.len() as f32);
-    layout.msg_text_x = layout.label_total_width + (layout.msg_total_width / 2.0);
-    layout.msg_text_width = layout.msg_total_width - 24.0;
+    // Color conversion to string. Accent colors are picked from the background as it
+    // appears over the page, in case it is translucent, and from the average of a
+    // gradient. Extra segments without a color get the default message color.
+    let page_color = verify_color(&badge.page_color)?;
+    let default_color = Badge::default().msg_color;
+    let fills = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| match segment.color.as_str() {
+            "" if i > 1 => Fill::parse(&default_color),
+            color => Fill::parse(color),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, (segment, segment_layout)) in segments.iter().zip(&mut layout.segments).enumerate() {
+        let accent_colors = match metrics.fixed_colors {
+            Some(fixed) => fixed.accent_colors,
+            None => get_accent_colors(fills[i].average_luminance(&page_color)),
+        };
+        segment_layout.text_color = color_or(&segment.text_color, accent_colors.text_color)?;
+        segment_layout.shadow_color = color_or(&segment.shadow_color, accent_colors.shadow_color)?;
+
+        // Without label text, the label side blends into the message unless the logo
+        // stands in for the label
+        let blends_into_msg = i == 0 && !has_label && !badge.use_logo_as_label;
+        let mut fill = fills[if blends_into_msg { 1 } else { i }].clone();
+        let fixed_background = metrics.fixed_colors.map(|fixed| {
+            if i == 0 {
+                fixed.label_background
+            } else {
+                fixed.msg_background
+            }
+        });
+
+        // Contrast between the text and background
+        if !segment_layout.text_norm.is_empty() {
+            segment_layout.contrast = Some(side_contrast(
+                badge.contrast_mode,
+                &page_color,
+                fixed_background,
+                &mut fill,
+                &mut segment_layout.text_color,
+            )?);
+        }
 
-    // Scale back up for the SVG
-    layout.label_text_width *= 10.0;
-    layout.msg_text_width *= 10.0;
-    layout.label_text_x *= 10.0;
-    layout.msg_text_x *= 10.0;
+        // Styles with fixed colors draw their own backgrounds, whatever the badge asks
+        // for
+        if let Some(fixed_background) = fixed_background {
+            fill = Fill::Solid(verify_color(fixed_background)?);
+        }
 
-    let label_color = verify_color(&badge.label_color)?;
-    let msg_color = verify_color(&badge.msg_color)?;
-    let label_accent_colors = get_accent_colors(&label_color);
-    let msg_accent_colors = get_accent_colors(&msg_color);
+        // Gradients are drawn from a definition in the SVG, which the fill refers to
+        (segment_layout.color, segment_layout.gradient) = match fill {
+            Fill::Solid(color) => (format_color(&color), None),
+            Fill::Gradient(gradient) => (String::new(), Some(gradient)),
+        };
 
-    if badge.label_text.is_empty() && !badge.use_logo_as_label {
-        layout.label_color = format_color(&msg_color);
-    } else {
-        layout.label_color = format_color(&label_color);
+        // Dark mode colors, if any were given
+        let dark_background = if blends_into_msg {
+            &segments[1].dark_color
+        } else {
+            &segment.dark_color
+        };
+        (
+            segment_layout.dark_color,
+            segment_layout.dark_text_color,
+            segment_layout.dark_shadow_color,
+        ) = dark_side_colors(dark_background, &segment.dark_text_color)?;
     }
-    layout.msg_color = format_color(&msg_color);
-    layout.label_text_color = label_accent_colors.text_color.to_string();
-    layout.msg_text_color = msg_accent_colors.text_color.to_string();
-    layout.label_shadow_color = label_accent_colors.shadow_color.to_string();
-    layout.msg_shadow_color = msg_accent_colors.shadow_color.to_string();
 
     Ok(layout)
 }
 
-/// Generate the correct layout information for a Social badge
-pub(crate) fn social(badge: &Badge) -> Result<Layout, BadgeError> {
-    let mut layout = Layout::default();
-
-    // Normalize text
-    let font = load_font(include_bytes!("OpenSans-Regular.ttf"))?;
-    let cap_label = uppercase_first_letter(&badge.label_text);
-    let (label_text_norm, label_text_width) = get_text_dims(&font, &cap_label, 16.0);
-    layout.label_text_norm = label_text_norm;
-    layout.label_text_width = label_text_width;
-    let (msg_text_norm, msg_text_width) = get_text_dims(&font, &badge.msg_text, 16.0);
-    layout.msg_text_norm = msg_text_norm;
-    layout.msg_text_width = msg_text_width;
-
-    // Padding and spacing calculations
-    let label_horiz_padding = 5.0;
-    let msg_horiz_padding = 4.0;
-    layout.badge_height = 20.0;
-
-    // Logo padding and width
-    let mut total_logo_width = 0.0;
-    layout.logo_width = 0.0;
-    layout.logo_padding = 0.0;
-    if !badge.logo.is_empty() {
-        if !badge.label_text.is_empty() {
-            layout.logo_padding = 3.0;
+/// Replace the colors of `layout` with its dark mode colors, for a badge that is always
+/// dark.
+pub(crate) fn use_dark_colors(layout: &mut Layout) {
+    for segment in layout.segments.iter_mut() {
+        if !segment.dark_color.is_empty() {
+            segment.color = std::mem::take(&mut segment.dark_color);
+            segment.gradient = None;
+        }
+        if !segment.dark_text_color.is_empty() {
+            segment.text_color = std::mem::take(&mut segment.dark_text_color);
+        }
+        if !segment.dark_shadow_color.is_empty() {
+            segment.shadow_color = std::mem::take(&mut segment.dark_shadow_color);
         }
-        let logo_height = 14.0;
-        layout.logo_y = (layout.badge_height - logo_height) * 0.5;
-        layout.logo_x = label_horiz_padding;
-        layout.logo_width = 14.0;
-        total_logo_width = layout.logo_width + layout.logo_padding;
     }
+}
 
-    // Label padding and width
-    layout.label_rect_width =
-        layout.label_text_width + total_logo_width + (2.0 * label_horiz_padding);
-    layout.label_text_x = total_logo_width + (layout.label_text_width / 2.0) + label_horiz_padding;
-    layout.label_total_width = layout.label_rect_width + 1.0;
-
-    // Message padding and width
-    let horiz_gutter = 6.0;
-    layout.msg_rect_width = layout.msg_text_width + (2.0 * msg_horiz_padding);
-    layout.msg_text_x = layout.label_rect_width + horiz_gutter + (layout.msg_rect_width / 2.0);
-    layout.msg_bubble_x = layout.label_rect_width + horiz_gutter + 0.5;
-    if layout.msg_text_norm.is_empty() {
-        layout.msg_total_width = 0.0;
+/// Reduce an internal layout to the public geometry of the badge.
+pub(crate) fn badge_layout(layout: &Layout) -> BadgeLayout {
+    let inset = 0.5 * layout.outline_width;
+    let rect_height = layout.badge_height - layout.outline_width;
+    let logo = if layout.logo_width > 0.0 {
+        Some(Rect {
+            x: layout.logo_x,
+            y: layout.logo_y,
+            width: layout.logo_width,
+            height: layout.logo_width,
+        })
     } else {
-        layout.msg_total_width = layout.msg_rect_width + horiz_gutter;
+        None
+    };
+    let mut segments = layout.segments.iter().map(|segment| SegmentBox {
+        rect: Rect {
+            x: segment.rect_x,
+            y: inset,
+            width: segment.rect_width,
+            height: rect_height,
+        },
+        text: TextBox {
+            center_x: segment.text_x,
+            baseline_y: layout.text_y,
+            width: segment.text_width,
+        },
+    });
+    let label = segments.next().unwrap_or_default();
+    let message = segments.next().unwrap_or_default();
+
+    BadgeLayout {
+        width: layout
+            .segments
+            .iter()
+            .map(|segment| segment.slot_width)
+            .sum(),
+        height: layout.badge_height,
+        label: label.rect,
+        message: message.rect,
+        label_text: label.text,
+        msg_text: message.text,
+        extra_segments: segments.collect(),
+        logo,
     }
-
-    // Scale back up for the SVG
-    layout.label_text_width *= 10.0;
-    layout.msg_text_width *= 10.0;
-    layout.label_text_x *= 10.0;
-    layout.msg_text_x *= 10.0;
-
-    // Color conversion to string
-    let label_color = verify_color(&badge.label_color)?;
-    let msg_color = verify_color(&badge.msg_color)?;
-    let label_accent_colors = get_accent_colors(&label_color);
-    let msg_accent_colors = get_accent_colors(&msg_color);
-
-    layout.label_color = format_color(&label_color);
-    layout.msg_color = format_color(&msg_color);
-    layout.label_text_color = label_accent_colors.text_color.to_string();
-    layout.msg_text_color = msg_accent_colors.text_color.to_string();
-    layout.label_shadow_color = label_accent_colors.shadow_color.to_string();
-    layout.msg_shadow_color = msg_accent_colors.shadow_color.to_string();
-
-    Ok(layout)
 }
@@ -31,43 +31,232 @@
 
 use super::badge_type::*;
 use super::format_helper;
+use super::gradient::Gradient;
 use askama::Template;
 use rand::{distributions::Alphanumeric, Rng};
 
+/// Text is drawn at ten times its size, then scaled back down with
+/// `transform="scale(0.1)"`, so that browsers render it more precisely.
+const TEXT_SCALE: f32 = 10.0;
+
+/// A random suffix that keeps the IDs inside a badge unique when several badges share
+/// a page.
+pub(crate) fn random_id_suffix() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(7)
+        .map(char::from)
+        .collect()
+}
+
+/// Check that `id_suffix` only holds characters that are valid in IDs and CSS class
+/// names.
+pub(crate) fn check_id_suffix(id_suffix: &str) -> Result<(), BadgeError> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if !id_suffix.chars().all(valid) {
+        return Err(BadgeError::InvalidIdSuffix(String::from(id_suffix)));
+    }
+    Ok(())
+}
+
+/// A gradient definition, ready to be written into a template.
+#[derive(Debug)]
+struct GradientFill {
+    /// unique ID of the gradient
+    id: String,
+    /// start of the gradient line in x
+    x1: String,
+    /// start of the gradient line in y
+    y1: String,
+    /// end of the gradient line in x
+    x2: String,
+    /// end of the gradient line in y
+    y2: String,
+    /// color stops of the gradient
+    stops: Vec<GradientFillStop>,
+}
+
+/// One color stop of a [GradientFill].
+#[derive(Debug)]
+struct GradientFillStop {
+    /// position along the gradient line
+    offset: String,
+    /// opaque color at this position
+    color: String,
+    /// opacity at this position, empty when fully opaque
+    opacity: String,
+}
+
+/// Format a fraction from 0 to 1 with at most three decimals.
+fn format_fraction(value: f32) -> String {
+    // Adding zero turns -0 into 0
+    ((value * 1000.0).round() / 1000.0 + 0.0).to_string()
+}
+
+impl GradientFill {
+    /// Prepare `gradient` to be written with the given ID.
+    fn new(id: String, gradient: &Gradient) -> GradientFill {
+        let stops = gradient
+            .stops
+            .iter()
+            .map(|stop| {
+                let mut color = stop.color;
+                color.alpha = 1.0;
+                GradientFillStop {
+                    offset: format_fraction(stop.offset),
+                    color: format_helper::format_color(&color),
+                    opacity: if stop.color.alpha < 1.0 {
+                        format_fraction(stop.color.alpha)
+                    } else {
+                        String::new()
+                    },
+                }
+            })
+            .collect();
+        GradientFill {
+            id,
+            x1: format_fraction(gradient.x1),
+            y1: format_fraction(gradient.y1),
+            x2: format_fraction(gradient.x2),
+            y2: format_fraction(gradient.y2),
+            stops,
+        }
+    }
+}
+
+/// One segment of a badge, ready to be written into a template.
+#[derive(Debug)]
+struct SegmentTemplate<'a> {
+    /// normalized text
+    text: &'a str,
+    /// whether the text reads right-to-left
+    text_rtl: bool,
+    /// URL link for the segment
+    link: &'a str,
+    /// title for the segment
+    title: &'a str,
+    /// background color, or a reference to its gradient
+    color: &'a str,
+    /// text color
+    text_color: &'a str,
+    /// shadow color
+    shadow_color: &'a str,
+    /// placement in x of the part of the badge that this segment takes up
+    slot_x: f32,
+    /// width of the part of the badge that this segment takes up
+    slot_width: f32,
+    /// placement of the background rectangle in x
+    rect_x: f32,
+    /// width of the background rectangle
+    rect_width: f32,
+    /// text placement in x
+    text_x: f32,
+    /// width of the text
+    text_width: f32,
+}
+
+/// Prepare the segments of a badge to be written into a template.
+fn segment_templates(layout: &Layout) -> Vec<SegmentTemplate<'_>> {
+    layout
+        .segments
+        .iter()
+        .map(|segment| SegmentTemplate {
+            text: &segment.text_norm,
+            text_rtl: segment.text_rtl,
+            link: &segment.link,
+            title: &segment.title,
+            color: &segment.color,
+            text_color: &segment.text_color,
+            shadow_color: &segment.shadow_color,
+            slot_x: segment.slot_x,
+            slot_width: segment.slot_width,
+            rect_x: segment.rect_x,
+            rect_width: segment.rect_width,
+            text_x: segment.text_x * TEXT_SCALE,
+            text_width: segment.text_width * TEXT_SCALE,
+        })
+        .collect()
+}
+
+/// The total width of a badge, in px.
+fn badge_width(layout: &Layout) -> f32 {
+    layout
+        .segments
+        .iter()
+        .map(|segment| segment.slot_width)
+        .sum()
+}
+
+/// Point the fill of each segment with a gradient at a definition of that gradient,
+/// and return the definitions.
+fn gradient_fills(layout: &mut Layout, id_suffix: &str) -> Vec<GradientFill> {
+    let mut fills = Vec::new();
+    for (i, segment) in layout.segments.iter_mut().enumerate() {
+        if let Some(gradient) = &segment.gradient {
+            let id = format!("seg{}_fill{}", i, id_suffix);
+            segment.color = format!("url(#{})", id);
+            fills.push(GradientFill::new(id, gradient));
+        }
+    }
+    fills
+}
+
+/// The `@media (prefers-color-scheme: dark)` rules that apply the dark colors of a
+/// badge, or an empty string if it has none. The rules select classes ending in
+/// `id_suffix`, so that they don't reach other badges on the same page.
+fn dark_style(layout: &Layout, id_suffix: &str) -> String {
+    if !layout.has_dark_colors() {
+        return String::new();
+    }
+    let mut rules = String::new();
+    for (i, segment) in layout.segments.iter().enumerate() {
+        // Only the bubbles after the first segment of Social badges have a stroke
+        let stroke_color = if i == 0 { "" } else { &segment.dark_color };
+        let segment_rules = [
+            ("fill", "fill", segment.dark_color.as_str()),
+            ("stroke", "stroke", stroke_color),
+            ("text", "fill", &segment.dark_text_color),
+            ("shadow", "fill", &segment.dark_shadow_color),
+        ];
+        for (class, property, color) in segment_rules {
+            if !color.is_empty() {
+                rules += &format!(".seg{}-{}{}{{{}:{}}}", i, class, id_suffix, property, color);
+            }
+        }
+    }
+    format!("@media (prefers-color-scheme: dark){{{}}}", rules)
+}
+
+/// Minify SVG string
+fn minify_svg_str(svg_str: String) -> String {
+    svg_str
+        .chars()
+        .filter(|c| *c != '\r' && *c != '\n')
+        .collect::<String>()
+}
+
 #[derive(Template, Debug)]
 #[template(path = "badge_template_flat.xml", escape = "xml")]
 /// Holds all information necessary for a Flat badge template.
 struct BadgeTemplateFlat<'a> {
-    /// text for label (left side)
-    label_text: &'a str,
-    /// text for message (right side)
-    msg_text: &'a str,
+    /// segments of the badge, from left to right
+    segments: &'a [SegmentTemplate<'a>],
     /// link for entire badge
     badge_link: &'a str,
-    /// URL link for label
-    label_link: &'a str,
-    /// URL link for message
-    msg_link: &'a str,
-    /// color for label
-    label_color: &'a str,
-    /// text color for label
-    label_text_color: &'a str,
-    /// shadow color for label
-    label_shadow_color: &'a str,
-    /// color for message
-    msg_color: &'a str,
-    /// text color for message
-    msg_text_color: &'a str,
-    /// shadow color for message
-    msg_shadow_color: &'a str,
+    /// gradients used as fills
+    fill_gradients: &'a [GradientFill],
+    /// dark mode style rules, if the badge has dark colors
+    dark_style: &'a str,
+    /// suffix of the classes the dark mode rules apply to
+    dark_class: &'a str,
     /// link to logo, placed on label side
     logo: &'a str,
+    /// whether to draw text without shadows
+    hide_shadows: bool,
+    /// accessible name of badge
+    aria_label: &'a str,
     /// title of badge
     full_badge_title: &'a str,
-    /// title for label
-    label_title: &'a str,
-    /// title for message
-    msg_title: &'a str,
     /// height of the badge, in px
     badge_height: f32,
     /// logo width of the badge, in px
@@ -76,71 +265,48 @@ struct BadgeTemplateFlat<'a> {
     logo_x: f32,
     /// logo placement in y
     logo_y: f32,
-    /// width of text for label, in px
-    label_text_width: f32,
-    /// width of text for message, in px
-    msg_text_width: f32,
-    /// logo text placement in x
-    label_text_x: f32,
-    /// message text placement in x
-    msg_text_x: f32,
-    /// width of left side of badge
-    left_width: f32,
-    /// width of right side of badge
-    right_width: f32,
+    /// text baseline placement in y
+    text_y: f32,
+    /// width of the entire badge
+    width: f32,
     /// unique ID for smooth gradient
     id_smooth: &'a str,
     /// unique ID for round shape
     id_round: &'a str,
 }
 
-/// Minify SVG string
-fn minify_svg_str(svg_str: String) -> String {
-    svg_str
-        .chars()
-        .filter(|c| *c != '\r' && *c != '\n')
-        .collect::<String>()
-}
-
 /// Generate the SVG string corresponding to a Flat badge with this Badge info
-pub(crate) fn flat_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeError> {
-    let id_suffix: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(7)
-        .map(char::from)
-        .collect();
+pub(crate) fn flat_svg(
+    badge: &Badge,
+    mut layout: Layout,
+    id_suffix: &str,
+) -> Result<String, BadgeError> {
     let id_smooth = format!("smooth{}", id_suffix);
     let id_round = format!("round{}", id_suffix);
+    let fill_gradients = gradient_fills(&mut layout, id_suffix);
+    let dark_style = dark_style(&layout, id_suffix);
+    let dark_class = if dark_style.is_empty() { "" } else { id_suffix };
     let mut logo_uri = badge.logo.clone();
     if badge.embed_logo {
         logo_uri = format_helper::attempt_logo_download(&badge.logo)?;
     }
+    let segments = segment_templates(&layout);
     let flat_badge = BadgeTemplateFlat {
-        label_text: &layout.label_text_norm,
-        msg_text: &layout.msg_text_norm,
+        segments: &segments,
         badge_link: &badge.badge_link,
-        label_link: &badge.label_link,
-        msg_link: &badge.msg_link,
-        label_color: &layout.label_color,
-        label_text_color: &layout.label_text_color,
-        label_shadow_color: &layout.label_shadow_color,
-        msg_color: &layout.msg_color,
-        msg_text_color: &layout.msg_text_color,
-        msg_shadow_color: &layout.msg_shadow_color,
+        fill_gradients: &fill_gradients,
+        dark_style: &dark_style,
+        dark_class,
         logo: &logo_uri,
-        full_badge_title: &badge.badge_title,
-        label_title: &badge.label_title,
-        msg_title: &badge.msg_title,
+        hide_shadows: badge.hide_shadows,
+        aria_label: &layout.aria_label,
+        full_badge_title: &layout.badge_title,
         badge_height: layout.badge_height,
         logo_width: layout.logo_width,
         logo_x: layout.logo_x,
         logo_y: layout.logo_y,
-        label_text_width: layout.label_text_width,
-        msg_text_width: layout.msg_text_width,
-        label_text_x: layout.label_text_x,
-        msg_text_x: layout.msg_text_x,
-        left_width: layout.label_total_width,
-        right_width: layout.msg_total_width,
+        text_y: layout.text_y * TEXT_SCALE,
+        width: badge_width(&layout),
         id_smooth: &id_smooth,
         id_round: &id_round,
     };
@@ -151,36 +317,24 @@ pub(crate) fn flat_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeErr
 #[template(path = "badge_template_plastic.xml", escape = "xml")]
 /// Holds all information necessary for a Plastic badge template.
 struct BadgeTemplatePlastic<'a> {
-    /// text for label (left side)
-    label_text: &'a str,
-    /// text for message (right side)
-    msg_text: &'a str,
+    /// segments of the badge, from left to right
+    segments: &'a [SegmentTemplate<'a>],
     /// link for entire badge
     badge_link: &'a str,
-    /// URL link for label
-    label_link: &'a str,
-    /// URL link for message
-    msg_link: &'a str,
-    /// color for label
-    label_color: &'a str,
-    /// text color for label
-    label_text_color: &'a str,
-    /// shadow color for label
-    label_shadow_color: &'a str,
-    /// color for message
-    msg_color: &'a str,
-    /// text color for message
-    msg_text_color: &'a str,
-    /// shadow color for message
-    msg_shadow_color: &'a str,
+    /// gradients used as fills
+    fill_gradients: &'a [GradientFill],
+    /// dark mode style rules, if the badge has dark colors
+    dark_style: &'a str,
+    /// suffix of the classes the dark mode rules apply to
+    dark_class: &'a str,
     /// link to logo, placed on label side
     logo: &'a str,
+    /// whether to draw text without shadows
+    hide_shadows: bool,
+    /// accessible name of badge
+    aria_label: &'a str,
     /// title of badge
     full_badge_title: &'a str,
-    /// title for label
-    label_title: &'a str,
-    /// title for message
-    msg_title: &'a str,
     /// height of the badge, in px
     badge_height: f32,
     /// logo width of the badge, in px
@@ -189,18 +343,10 @@ struct BadgeTemplatePlastic<'a> {
     logo_x: f32,
     /// logo placement in y
     logo_y: f32,
-    /// width of text for label, in px
-    label_text_width: f32,
-    /// width of text for message, in px
-    msg_text_width: f32,
-    /// logo text placement in x
-    label_text_x: f32,
-    /// message text placement in x
-    msg_text_x: f32,
-    /// width of left side of badge
-    left_width: f32,
-    /// width of right side of badge
-    right_width: f32,
+    /// text baseline placement in y
+    text_y: f32,
+    /// width of the entire badge
+    width: f32,
     /// unique ID for smooth gradient
     id_smooth: &'a str,
     /// unique ID for round shape
@@ -208,44 +354,37 @@ struct BadgeTemplatePlastic<'a> {
 }
 
 /// Generate the SVG string corresponding to a Plastic badge with this Badge info
-pub(crate) fn plastic_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeError> {
-    let id_suffix: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(7)
-        .map(char::from)
-        .collect();
+pub(crate) fn plastic_svg(
+    badge: &Badge,
+    mut layout: Layout,
+    id_suffix: &str,
+) -> Result<String, BadgeError> {
     let id_smooth = format!("smooth{}", id_suffix);
     let id_round = format!("round{}", id_suffix);
+    let fill_gradients = gradient_fills(&mut layout, id_suffix);
+    let dark_style = dark_style(&layout, id_suffix);
+    let dark_class = if dark_style.is_empty() { "" } else { id_suffix };
     let mut logo_uri = badge.logo.clone();
     if badge.embed_logo {
         logo_uri = format_helper::attempt_logo_download(&badge.logo)?;
     }
+    let segments = segment_templates(&layout);
     let plastic_badge = BadgeTemplatePlastic {
-        label_text: &layout.label_text_norm,
-        msg_text: &layout.msg_text_norm,
+        segments: &segments,
         badge_link: &badge.badge_link,
-        label_link: &badge.label_link,
-        msg_link: &badge.msg_link,
-        label_color: &layout.label_color,
-        label_text_color: &layout.label_text_color,
-        label_shadow_color: &layout.label_shadow_color,
-        msg_color: &layout.msg_color,
-        msg_text_color: &layout.msg_text_color,
-        msg_shadow_color: &layout.msg_shadow_color,
+        fill_gradients: &fill_gradients,
+        dark_style: &dark_style,
+        dark_class,
         logo: &logo_uri,
-        full_badge_title: &badge.badge_title,
-        label_title: &badge.label_title,
-        msg_title: &badge.msg_title,
+        hide_shadows: badge.hide_shadows,
+        aria_label: &layout.aria_label,
+        full_badge_title: &layout.badge_title,
         badge_height: layout.badge_height,
         logo_width: layout.logo_width,
         logo_x: layout.logo_x,
         logo_y: layout.logo_y,
-        label_text_width: layout.label_text_width,
-        msg_text_width: layout.msg_text_width,
-        label_text_x: layout.label_text_x,
-        msg_text_x: layout.msg_text_x,
-        left_width: layout.label_total_width,
-        right_width: layout.msg_total_width,
+        text_y: layout.text_y * TEXT_SCALE,
+        width: badge_width(&layout),
         id_smooth: &id_smooth,
         id_round: &id_round,
     };
@@ -256,32 +395,22 @@ pub(crate) fn plastic_svg(badge: &Badge, layout: Layout) -> Result<String, Badge
 #[template(path = "badge_template_flat_square.xml", escape = "xml")]
 /// Holds all information necessary for a Flat Square badge template.
 struct BadgeTemplateFlatSquare<'a> {
-    /// text for label (left side)
-    label_text: &'a str,
-    /// text for message (right side)
-    msg_text: &'a str,
+    /// segments of the badge, from left to right
+    segments: &'a [SegmentTemplate<'a>],
     /// link for entire badge
     badge_link: &'a str,
-    /// URL link for label
-    label_link: &'a str,
-    /// URL link for message
-    msg_link: &'a str,
-    /// color for label
-    label_color: &'a str,
-    /// text color for label
-    label_text_color: &'a str,
-    /// color for message
-    msg_color: &'a str,
-    /// text color for message
-    msg_text_color: &'a str,
+    /// gradients used as fills
+    fill_gradients: &'a [GradientFill],
+    /// dark mode style rules, if the badge has dark colors
+    dark_style: &'a str,
+    /// suffix of the classes the dark mode rules apply to
+    dark_class: &'a str,
     /// link to logo, placed on label side
     logo: &'a str,
+    /// accessible name of badge
+    aria_label: &'a str,
     /// title of badge
     full_badge_title: &'a str,
-    /// title for label
-    label_title: &'a str,
-    /// title for message
-    msg_title: &'a str,
     /// height of the badge, in px
     badge_height: f32,
     /// logo width of the badge, in px
@@ -290,50 +419,41 @@ struct BadgeTemplateFlatSquare<'a> {
     logo_x: f32,
     /// logo placement in y
     logo_y: f32,
-    /// width of text for label, in px
-    label_text_width: f32,
-    /// width of text for message, in px
-    msg_text_width: f32,
-    /// logo text placement in x
-    label_text_x: f32,
-    /// message text placement in x
-    msg_text_x: f32,
-    /// width of left side of badge
-    left_width: f32,
-    /// width of right side of badge
-    right_width: f32,
+    /// text baseline placement in y
+    text_y: f32,
+    /// width of the entire badge
+    width: f32,
 }
 
 /// Generate the SVG string corresponding to a Flat Square badge with this Badge info
-pub(crate) fn flat_square_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeError> {
+pub(crate) fn flat_square_svg(
+    badge: &Badge,
+    mut layout: Layout,
+    id_suffix: &str,
+) -> Result<String, BadgeError> {
+    let fill_gradients = gradient_fills(&mut layout, id_suffix);
+    let dark_style = dark_style(&layout, id_suffix);
+    let dark_class = if dark_style.is_empty() { "" } else { id_suffix };
     let mut logo_uri = badge.logo.clone();
     if badge.embed_logo {
         logo_uri = format_helper::attempt_logo_download(&badge.logo)?;
     }
+    let segments = segment_templates(&layout);
     let flat_square_badge = BadgeTemplateFlatSquare {
-        label_text: &layout.label_text_norm,
-        msg_text: &layout.msg_text_norm,
+        segments: &segments,
         badge_link: &badge.badge_link,
-        label_link: &badge.label_link,
-        msg_link: &badge.msg_link,
-        label_color: &layout.label_color,
-        label_text_color: &layout.label_text_color,
-        msg_color: &layout.msg_color,
-        msg_text_color: &layout.msg_text_color,
+        fill_gradients: &fill_gradients,
+        dark_style: &dark_style,
+        dark_class,
         logo: &logo_uri,
-        full_badge_title: &badge.badge_title,
-        label_title: &badge.label_title,
-        msg_title: &badge.msg_title,
+        aria_label: &layout.aria_label,
+        full_badge_title: &layout.badge_title,
         badge_height: layout.badge_height,
         logo_width: layout.logo_width,
         logo_x: layout.logo_x,
         logo_y: layout.logo_y,
-        label_text_width: layout.label_text_width,
-        msg_text_width: layout.msg_text_width,
-        label_text_x: layout.label_text_x,
-        msg_text_x: layout.msg_text_x,
-        left_width: layout.label_total_width,
-        right_width: layout.msg_total_width,
+        text_y: layout.text_y * TEXT_SCALE,
+        width: badge_width(&layout),
     };
     Ok(minify_svg_str(flat_square_badge.render().unwrap()))
 }
@@ -342,32 +462,22 @@ pub(crate) fn flat_square_svg(badge: &Badge, layout: Layout) -> Result<String, B
 #[template(path = "badge_template_forthebadge.xml", escape = "xml")]
 /// Holds all information necessary for a "For the Badge" badge template.
 struct BadgeTemplateForTheBadge<'a> {
-    /// text for label (left side)
-    label_text: &'a str,
-    /// text for message (right side)
-    msg_text: &'a str,
+    /// segments of the badge, from left to right
+    segments: &'a [SegmentTemplate<'a>],
     /// link for entire badge
     badge_link: &'a str,
-    /// URL link for label
-    label_link: &'a str,
-    /// URL link for message
-    msg_link: &'a str,
-    /// color for label
-    label_color: &'a str,
-    /// text color for label
-    label_text_color: &'a str,
-    /// color for message
-    msg_color: &'a str,
-    /// text color for message
-    msg_text_color: &'a str,
+    /// gradients used as fills
+    fill_gradients: &'a [GradientFill],
+    /// dark mode style rules, if the badge has dark colors
+    dark_style: &'a str,
+    /// suffix of the classes the dark mode rules apply to
+    dark_class: &'a str,
     /// link to logo, placed on label side
     logo: &'a str,
+    /// accessible name of badge
+    aria_label: &'a str,
     /// title of badge
     full_badge_title: &'a str,
-    /// title for label
-    label_title: &'a str,
-    /// title for message
-    msg_title: &'a str,
     /// height of the badge, in px
     badge_height: f32,
     /// logo width of the badge, in px
@@ -376,50 +486,41 @@ struct BadgeTemplateForTheBadge<'a> {
     logo_x: f32,
     /// logo placement in y
     logo_y: f32,
-    /// width of text for label, in px
-    label_text_width: f32,
-    /// width of text for message, in px
-    msg_text_width: f32,
-    /// logo text placement in x
-    label_text_x: f32,
-    /// message text placement in x
-    msg_text_x: f32,
-    /// width of left side of badge
-    left_width: f32,
-    /// width of right side of badge
-    right_width: f32,
+    /// text baseline placement in y
+    text_y: f32,
+    /// width of the entire badge
+    width: f32,
 }
 
 /// Generate the SVG string corresponding to a "for the badge" badge with this Badge info
-pub(crate) fn for_the_badge_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeError> {
+pub(crate) fn for_the_badge_svg(
+    badge: &Badge,
+    mut layout: Layout,
+    id_suffix: &str,
+) -> Result<String, BadgeError> {
+    let fill_gradients = gradient_fills(&mut layout, id_suffix);
+    let dark_style = dark_style(&layout, id_suffix);
+    let dark_class = if dark_style.is_empty() { "" } else { id_suffix };
     let mut logo_uri = badge.logo.clone();
     if badge.embed_logo {
         logo_uri = format_helper::attempt_logo_download(&badge.logo)?;
     }
+    let segments = segment_templates(&layout);
     let forthebadge_badge = BadgeTemplateForTheBadge {
-        label_text: &layout.label_text_norm,
-        msg_text: &layout.msg_text_norm,
+        segments: &segments,
         badge_link: &badge.badge_link,
-        label_link: &badge.label_link,
-        msg_link: &badge.msg_link,
-        label_color: &layout.label_color,
-        label_text_color: &layout.label_text_color,
-        msg_color: &layout.msg_color,
-        msg_text_color: &layout.msg_text_color,
+        fill_gradients: &fill_gradients,
+        dark_style: &dark_style,
+        dark_class,
         logo: &logo_uri,
-        full_badge_title: &badge.badge_title,
-        label_title: &badge.label_title,
-        msg_title: &badge.msg_title,
+        aria_label: &layout.aria_label,
+        full_badge_title: &layout.badge_title,
         badge_height: layout.badge_height,
         logo_width: layout.logo_width,
         logo_x: layout.logo_x,
         logo_y: layout.logo_y,
-        label_text_width: layout.label_text_width,
-        msg_text_width: layout.msg_text_width,
-        label_text_x: layout.label_text_x,
-        msg_text_x: layout.msg_text_x,
-        left_width: layout.label_total_width,
-        right_width: layout.msg_total_width,
+        text_y: layout.text_y * TEXT_SCALE,
+        width: badge_width(&layout),
     };
     Ok(minify_svg_str(forthebadge_badge.render().unwrap()))
 }
@@ -428,18 +529,20 @@ pub(crate) fn for_the_badge_svg(badge: &Badge, layout: Layout) -> Result<String,
 #[template(path = "badge_template_social.xml", escape = "xml")]
 /// Holds all information necessary for a Social badge template.
 struct BadgeTemplateSocial<'a> {
-    /// text for label (left side)
-    label_text: &'a str,
-    /// text for message (right side)
-    msg_text: &'a str,
+    /// segments of the badge, from left to right
+    segments: &'a [SegmentTemplate<'a>],
     /// link for entire badge
     badge_link: &'a str,
-    /// URL link for label
-    label_link: &'a str,
-    /// URL link for message
-    msg_link: &'a str,
+    /// dark mode style rules, if the badge has dark colors
+    dark_style: &'a str,
+    /// suffix of the classes the dark mode rules apply to
+    dark_class: &'a str,
     /// link to logo, placed on label side
     logo: &'a str,
+    /// whether to draw text without shadows
+    hide_shadows: bool,
+    /// accessible name of badge
+    aria_label: &'a str,
     /// title of badge
     full_badge_title: &'a str,
     /// height of the badge, in px
@@ -450,66 +553,93 @@ struct BadgeTemplateSocial<'a> {
     logo_x: f32,
     /// logo placement in y
     logo_y: f32,
-    /// width of text for label, in px
-    label_text_width: f32,
-    /// width of text for message, in px
-    msg_text_width: f32,
-    /// logo text placement in x
-    label_text_x: f32,
-    /// message text placement in x
-    msg_text_x: f32,
-    /// width of left side of badge
-    left_width: f32,
-    /// width of right side of badge
-    right_width: f32,
+    /// text baseline placement in y
+    text_y: f32,
+    /// width of the entire badge
+    width: f32,
     /// unique ID for smooth gradient
     id_smooth: &'a str,
     /// unique ID for round shape
     id_round: &'a str,
-    /// message bubble placement in x
-    msg_bubble_x: f32,
+    /// unique ID for the label box, highlighted on hover
+    id_llink: &'a str,
+    /// unique ID for the message text, highlighted on hover
+    id_rlink: &'a str,
     /// width of label bounding box
     label_rect_width: f32,
-    /// width of message bounding box
-    msg_rect_width: f32,
 }
 
 /// Generate the SVG string corresponding to a Social badge with this Badge info
-pub(crate) fn social_svg(badge: &Badge, layout: Layout) -> Result<String, BadgeError> {
-    let id_suffix: String = rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(7)
-        .map(char::from)
-        .collect();
+pub(crate) fn social_svg(
+    badge: &Badge,
+    layout: Layout,
+    id_suffix: &str,
+) -> Result<String, BadgeError> {
     let id_smooth = format!("smooth{}", id_suffix);
     let id_round = format!("round{}", id_suffix);
+    let id_llink = format!("llink{}", id_suffix);
+    let id_rlink = format!("rlink{}", id_suffix);
+    let dark_style = dark_style(&layout, id_suffix);
+    let dark_class = if dark_style.is_empty() { "" } else { id_suffix };
     let mut logo_uri = badge.logo.clone();
     if badge.embed_logo {
         logo_uri = format_helper::attempt_logo_download(&badge.logo)?;
     }
+    let segments = segment_templates(&layout);
     let social_badge = BadgeTemplateSocial {
-        label_text: &layout.label_text_norm,
-        msg_text: &layout.msg_text_norm,
+        segments: &segments,
         badge_link: &badge.badge_link,
-        label_link: &badge.label_link,
-        msg_link: &badge.msg_link,
+        dark_style: &dark_style,
+        dark_class,
         logo: &logo_uri,
-        full_badge_title: &badge.badge_title,
+        hide_shadows: badge.hide_shadows,
+        aria_label: &layout.aria_label,
+        full_badge_title: &layout.badge_title,
         badge_height: layout.badge_height,
         logo_width: layout.logo_width,
         logo_x: layout.logo_x,
         logo_y: layout.logo_y,
-        label_text_width: layout.label_text_width,
-        msg_text_width: layout.msg_text_width,
-        label_text_x: layout.label_text_x,
-        msg_text_x: layout.msg_text_x,
-        left_width: layout.label_total_width,
-        right_width: layout.msg_total_width,
+        text_y: layout.text_y * TEXT_SCALE,
+        width: badge_width(&layout),
         id_smooth: &id_smooth,
         id_round: &id_round,
-        msg_bubble_x: layout.msg_bubble_x,
-        label_rect_width: layout.label_rect_width,
-        msg_rect_width: layout.msg_rect_width,
+        id_llink: &id_llink,
+        id_rlink: &id_rlink,
+        label_rect_width: layout.segments[0].rect_width,
     };
     Ok(minify_svg_str(social_badge.render().unwrap()))
 }
+
+#[derive(Template, Debug)]
+#[template(path = "badge_template_grid.xml", escape = "xml")]
+/// Holds all information necessary for a grid of badges.
+struct BadgeTemplateGrid<'a> {
+    /// every badge of the grid
+    badges: &'a [GridCell],
+    /// width of the entire grid
+    width: f32,
+    /// height of the entire grid
+    height: f32,
+}
+
+/// One badge of a grid, ready to be written into a template.
+#[derive(Debug)]
+pub(crate) struct GridCell {
+    /// the SVG of the badge
+    pub svg: String,
+    /// left edge of the badge in the grid
+    pub x: f32,
+    /// top edge of the badge in the grid
+    pub y: f32,
+}
+
+/// Generate the SVG string of a grid holding `badges`, each placed at its position in
+/// the grid.
+pub(crate) fn grid_svg(badges: &[GridCell], width: f32, height: f32) -> String {
+    let grid = BadgeTemplateGrid {
+        badges,
+        width,
+        height,
+    };
+    minify_svg_str(grid.render().unwrap())
+}
@@ -0,0 +1,262 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Parse badge fills, which are either a solid color or a CSS `linear-gradient(...)`
+
+use super::badge_type::BadgeError;
+use super::format_helper::{blend_over, relative_luminance, verify_color};
+use css_color::Rgba;
+
+/// How many points along a gradient are checked for luminance and contrast.
+const GRADIENT_SAMPLES: usize = 32;
+
+/// The background fill of one side of a badge.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Fill {
+    /// a single color
+    Solid(Rgba),
+    /// a linear gradient between two or more colors
+    Gradient(Gradient),
+}
+
+/// A linear gradient, in the bounding box units of the shape it fills.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Gradient {
+    /// start of the gradient line in x, from 0 to 1
+    pub x1: f32,
+    /// start of the gradient line in y, from 0 to 1
+    pub y1: f32,
+    /// end of the gradient line in x, from 0 to 1
+    pub x2: f32,
+    /// end of the gradient line in y, from 0 to 1
+    pub y2: f32,
+    /// color stops, in increasing order of offset
+    pub stops: Vec<GradientStop>,
+}
+
+/// One color of a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GradientStop {
+    /// position along the gradient line, from 0 to 1
+    pub offset: f32,
+    /// color at this position
+    pub color: Rgba,
+}
+
+impl Fill {
+    /// Parse a CSS color or `linear-gradient(...)`.
+    pub fn parse(spec: &str) -> Result<Fill, BadgeError> {
+        match spec.trim().strip_prefix("linear-gradient(") {
+            Some(args) => match args.strip_suffix(')').and_then(Gradient::parse) {
+                Some(gradient) => Ok(Fill::Gradient(gradient)),
+                None => Err(BadgeError::ColorNotValid(String::from(spec))),
+            },
+            None => Ok(Fill::Solid(verify_color(spec)?)),
+        }
+    }
+
+    /// Colors spread evenly across the fill.
+    pub fn samples(&self) -> Vec<Rgba> {
+        match self {
+            Fill::Solid(color) => vec![*color],
+            Fill::Gradient(gradient) => (0..GRADIENT_SAMPLES)
+                .map(|i| gradient.color_at(i as f32 / (GRADIENT_SAMPLES - 1) as f32))
+                .collect(),
+        }
+    }
+
+    /// Average relative luminance of the fill, as it appears over `page_color`.
+    pub fn average_luminance(&self, page_color: &Rgba) -> f32 {
+        let samples = self.samples();
+        let total: f32 = samples
+            .iter()
+            .map(|color| relative_luminance(&blend_over(color, page_color)))
+            .sum();
+        total / samples.len() as f32
+    }
+
+    /// Replace every color of the fill with `f` applied to it.
+    pub fn map_colors(&mut self, mut f: impl FnMut(&Rgba) -> Rgba) {
+        match self {
+            Fill::Solid(color) => *color = f(color),
+            Fill::Gradient(gradient) => {
+                for stop in gradient.stops.iter_mut() {
+                    stop.color = f(&stop.color);
+                }
+            }
+        }
+    }
+}
+
+/// Split `text` on the commas that aren't nested inside parentheses.
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Parse a CSS gradient direction, such as `to right` or `45deg`, into the angle of
+/// the gradient line in degrees, clockwise from pointing up.
+fn parse_direction(direction: &str) -> Option<f32> {
+    let direction = direction.to_ascii_lowercase();
+    if let Some(side) = direction.strip_prefix("to ") {
+        let (mut x, mut y) = (0.0, 0.0);
+        for word in side.split_whitespace() {
+            match word {
+                "top" => y = -1.0,
+                "bottom" => y = 1.0,
+                "left" => x = -1.0,
+                "right" => x = 1.0,
+                _ => return None,
+            }
+        }
+        return Some(f32::atan2(x, -y).to_degrees());
+    }
+    let units = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ];
+    units.iter().find_map(|(unit, to_degrees)| {
+        let value: f32 = direction.strip_suffix(unit)?.trim().parse().ok()?;
+        Some(value * to_degrees)
+    })
+}
+
+/// Parse a color stop, such as `red` or `#ff0 40%`, into its color and optional offset.
+fn parse_stop(stop: &str) -> Option<(Rgba, Option<f32>)> {
+    if let Some((color, position)) = stop.rsplit_once(char::is_whitespace) {
+        if let Some(percent) = position.strip_suffix('%') {
+            let offset = percent.parse::<f32>().ok()? / 100.0;
+            return Some((verify_color(color.trim()).ok()?, Some(offset)));
+        }
+    }
+    Some((verify_color(stop).ok()?, None))
+}
+
+impl Gradient {
+    /// Parse the arguments of a CSS `linear-gradient(...)`.
+    fn parse(args: &str) -> Option<Gradient> {
+        let mut parts = split_top_level(args);
+        // CSS gradients run top to bottom unless told otherwise
+        let mut angle = 180.0;
+        if let Some(parsed) = parts.first().and_then(|first| parse_direction(first)) {
+            angle = parsed;
+            parts.remove(0);
+        }
+        let stops = parts
+            .iter()
+            .map(|part| parse_stop(part))
+            .collect::<Option<Vec<_>>>()?;
+        if stops.len() < 2 {
+            return None;
+        }
+
+        // The first and last stops default to the ends of the gradient, and stops
+        // without an offset are spread evenly between the ones around them
+        let mut offsets: Vec<Option<f32>> = stops.iter().map(|(_, offset)| *offset).collect();
+        let last = offsets.len() - 1;
+        offsets[0] = offsets[0].or(Some(0.0));
+        offsets[last] = offsets[last].or(Some(1.0));
+        let mut prev = 0;
+        for i in 1..offsets.len() {
+            if let Some(offset) = offsets[i] {
+                let start = offsets[prev].unwrap();
+                for (j, missing) in offsets.iter_mut().enumerate().take(i).skip(prev + 1) {
+                    let t = (j - prev) as f32 / (i - prev) as f32;
+                    *missing = Some(start + (offset - start) * t);
+                }
+                prev = i;
+            }
+        }
+
+        // Offsets can't go backwards
+        let mut max_offset = 0.0_f32;
+        let stops = stops
+            .iter()
+            .zip(offsets)
+            .map(|((color, _), offset)| {
+                max_offset = max_offset.max(offset.unwrap().clamp(0.0, 1.0));
+                GradientStop {
+                    offset: max_offset,
+                    color: *color,
+                }
+            })
+            .collect();
+
+        let (dx, dy) = (angle.to_radians().sin(), -angle.to_radians().cos());
+        Some(Gradient {
+            x1: 0.5 - 0.5 * dx,
+            y1: 0.5 - 0.5 * dy,
+            x2: 0.5 + 0.5 * dx,
+            y2: 0.5 + 0.5 * dy,
+            stops,
+        })
+    }
+
+    /// The color at position `t` along the gradient line, from 0 to 1.
+    pub fn color_at(&self, t: f32) -> Rgba {
+        let first = self.stops[0];
+        if t <= first.offset {
+            return first.color;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.offset {
+                let span = b.offset - a.offset;
+                let amount = if span > 0.0 {
+                    (t - a.offset) / span
+                } else {
+                    1.0
+                };
+                let mix = |x: f32, y: f32| x + (y - x) * amount;
+                return Rgba::new(
+                    mix(a.color.red, b.color.red),
+                    mix(a.color.green, b.color.green),
+                    mix(a.color.blue, b.color.blue),
+                    mix(a.color.alpha, b.color.alpha),
+                );
+            }
+        }
+        self.stops[self.stops.len() - 1].color
+    }
+}
@@ -0,0 +1,184 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Ready-to-paste markup that shows a badge in a README or documentation page
+
+use super::badge_grid::BadgeGrid;
+use super::badge_type::Badge;
+use super::generate_layout;
+
+/// The markup language of a snippet that shows a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetFormat {
+    /// `[![alt](image)](link)`
+    Markdown,
+    /// `<a href="link"><img src="image" alt="alt"></a>`
+    Html,
+    /// An `.. image::` directive with `:alt:` and `:target:` options
+    ReStructuredText,
+    /// `image:image["alt",link="link"]`
+    AsciiDoc,
+}
+
+impl SnippetFormat {
+    /// The markup that shows the image at `image`, described by `alt`, and links to
+    /// `link` unless it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::SnippetFormat;
+    /// let snippet = SnippetFormat::Markdown.snippet("badge.svg", "build: passing", "");
+    /// assert_eq!(snippet, "![build: passing](badge.svg)");
+    /// ```
+    ///
+    pub fn snippet(&self, image: &str, alt: &str, link: &str) -> String {
+        // Snippets are a single line, apart from the options of a directive
+        let alt = alt.split_whitespace().collect::<Vec<_>>().join(" ");
+        match self {
+            SnippetFormat::Markdown => {
+                let image = format!("![{}]({})", escape_markdown(&alt), escape_url(image));
+                if link.is_empty() {
+                    image
+                } else {
+                    format!("[{}]({})", image, escape_url(link))
+                }
+            }
+            SnippetFormat::Html => {
+                let image = format!(
+                    "<img src=\"{}\" alt=\"{}\">",
+                    escape_html(image),
+                    escape_html(&alt)
+                );
+                if link.is_empty() {
+                    image
+                } else {
+                    format!("<a href=\"{}\">{}</a>", escape_html(link), image)
+                }
+            }
+            SnippetFormat::ReStructuredText => {
+                let mut directive = format!(".. image:: {}\n   :alt: {}", image, alt);
+                if !link.is_empty() {
+                    directive += &format!("\n   :target: {}", link);
+                }
+                directive
+            }
+            SnippetFormat::AsciiDoc => {
+                let mut attributes = format!("\"{}\"", alt.replace('"', "\\\""));
+                if !link.is_empty() {
+                    attributes += &format!(",link=\"{}\"", link.replace('"', "%22"));
+                }
+                format!("image:{}[{}]", escape_url(image), attributes)
+            }
+        }
+    }
+}
+
+impl Badge {
+    /// The markup that shows this badge, saved at `image`, in the given format.
+    ///
+    /// The alt text is the [badge_title](Badge::badge_title), or else the accessible
+    /// name of the badge, such as "build: passing". The snippet links to the
+    /// [badge_link](Badge::badge_link), if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, SnippetFormat};
+    /// let badge = Badge {
+    ///     label_text: String::from("build"),
+    ///     msg_text: String::from("passing"),
+    ///     badge_link: String::from("https://ci.example.com"),
+    ///     ..Badge::default()
+    /// };
+    /// assert_eq!(
+    ///     badge.snippet(SnippetFormat::Markdown, "build.svg"),
+    ///     "[![build: passing](build.svg)](https://ci.example.com)"
+    /// );
+    /// ```
+    ///
+    pub fn snippet(&self, format: SnippetFormat, image: &str) -> String {
+        format.snippet(image, &self.alt_text(), &self.badge_link)
+    }
+
+    /// The text that describes this badge in place of its image.
+    fn alt_text(&self) -> String {
+        if self.badge_title.is_empty() {
+            generate_layout::accessible_name(self)
+        } else {
+            self.badge_title.clone()
+        }
+    }
+}
+
+impl BadgeGrid {
+    /// The markup that shows this grid, saved at `image`, in the given format. The
+    /// alt text lists the alt text of each badge, as in [Badge::snippet].
+    ///
+    /// An image doesn't pass clicks on to the links inside it, so the snippet has no
+    /// link; embed the SVG itself to keep the links of each badge.
+    pub fn snippet(&self, format: SnippetFormat, image: &str) -> String {
+        let alt = self
+            .badges
+            .iter()
+            .map(|style| style.badge().alt_text())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format.snippet(image, &alt, "")
+    }
+}
+
+/// Escape the characters that would end the alt text of a Markdown image early.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Percent-encode the characters that would end a URL early in Markdown or AsciiDoc.
+fn escape_url(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+        .replace('[', "%5B")
+        .replace(']', "%5D")
+}
+
+/// Escape text for an HTML attribute value.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
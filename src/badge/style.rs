@@ -32,6 +32,7 @@
 use super::badge_type::*;
 use super::generate_layout;
 use super::generate_svg;
+use super::style_metrics::{self, StyleMetrics};
 
 /// A badge container used to format and generate a badge SVG.
 ///
@@ -78,6 +79,11 @@ impl Style {
     /// Badges are lazily evaluated; that is, the data in the Badge struct
     /// is not verified until a badge is generated using this function.
     ///
+    /// Text is measured with the bundled fonts, so the same badge has the same size
+    /// on every machine. With the `system-fonts` feature, characters the bundled fonts
+    /// lack are measured with an installed font instead, and the size then depends on
+    /// the fonts installed.
+    ///
     /// # Errors
     ///
     /// Since this is where data verification takes place, a number of errors are
@@ -87,26 +93,186 @@ impl Style {
     /// - [CannotEmbedLogo](BadgeError::CannotEmbedLogo)
     /// - [CannotLocateFont](BadgeError::CannotLocateFont)
     /// - [CannotLoadFont](BadgeError::CannotLoadFont)
+    /// - [InsufficientContrast](BadgeError::InsufficientContrast), with [ContrastMode::Strict]
     ///
     /// See [BadgeError] for a full description of each.
     ///
     pub fn generate_svg(&self) -> Result<String, BadgeError> {
-        let layout = match self {
-            Style::Flat(badge) => generate_layout::flat_or_square(badge)?,
-            Style::FlatSquare(badge) => generate_layout::flat_or_square(badge)?,
-            Style::Plastic(badge) => generate_layout::plastic(badge)?,
-            Style::ForTheBadge(badge) => generate_layout::for_the_badge(badge)?,
-            Style::Social(badge) => generate_layout::social(badge)?,
-        };
+        let layout = self.checked_layout()?;
+        self.render(layout, &generate_svg::random_id_suffix())
+    }
+
+    /// Generates an SVG like [generate_svg](Style::generate_svg), but ends every ID in
+    /// the badge with `id_suffix` instead of a random suffix, so the same badge always
+    /// generates the same SVG. Badges shown on the same page need different suffixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, Style};
+    /// let style = Style::Flat(Badge::default());
+    /// let svg = style.generate_svg_with_id_suffix("build").unwrap();
+    /// assert!(svg.contains("id=\"smoothbuild\""));
+    /// assert_eq!(svg, style.generate_svg_with_id_suffix("build").unwrap());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [generate_svg](Style::generate_svg), along with
+    /// [InvalidIdSuffix](BadgeError::InvalidIdSuffix) if `id_suffix` holds anything
+    /// but ASCII letters, digits, `-` and `_`.
+    ///
+    pub fn generate_svg_with_id_suffix(&self, id_suffix: &str) -> Result<String, BadgeError> {
+        generate_svg::check_id_suffix(id_suffix)?;
+        let layout = self.checked_layout()?;
+        self.render(layout, id_suffix)
+    }
 
+    /// Generates a light and a dark SVG of the badge, in that order, for pages that
+    /// pick an image by color scheme with `<picture>`:
+    ///
+    /// ```html
+    /// <picture>
+    ///   <source media="(prefers-color-scheme: dark)" srcset="badge-dark.svg">
+    ///   <img src="badge.svg" alt="build: passing">
+    /// </picture>
+    /// ```
+    ///
+    /// The light SVG uses the regular badge colors, and the dark SVG uses the dark
+    /// colors of the badge, such as [dark_label_color](Badge::dark_label_color),
+    /// wherever they are set. Neither depends on the viewer's color scheme, so both
+    /// also work where `<style>` rules are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, Style};
+    /// let badge = Badge {
+    ///     dark_msg_color: String::from("#0d1117"),
+    ///     ..Badge::default()
+    /// };
+    /// let (light, dark) = Style::Flat(badge).generate_svg_pair().unwrap();
+    /// assert!(light.contains("#007ec6"));
+    /// assert!(dark.contains("#0d1117"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The same as [generate_svg](Style::generate_svg).
+    ///
+    pub fn generate_svg_pair(&self) -> Result<(String, String), BadgeError> {
+        let mut light = self.checked_layout()?;
+        light.clear_dark_colors();
+        let mut dark = self.checked_layout()?;
+        generate_layout::use_dark_colors(&mut dark);
+        Ok((
+            self.render(light, &generate_svg::random_id_suffix())?,
+            self.render(dark, &generate_svg::random_id_suffix())?,
+        ))
+    }
+
+    /// Generate the SVG for an already computed layout, with the given ID suffix.
+    pub(super) fn render(&self, layout: Layout, id_suffix: &str) -> Result<String, BadgeError> {
         let style = match self {
-            Style::Flat(badge) => generate_svg::flat_svg(badge, layout)?,
-            Style::FlatSquare(badge) => generate_svg::flat_square_svg(badge, layout)?,
-            Style::Plastic(badge) => generate_svg::plastic_svg(badge, layout)?,
-            Style::ForTheBadge(badge) => generate_svg::for_the_badge_svg(badge, layout)?,
-            Style::Social(badge) => generate_svg::social_svg(badge, layout)?,
+            Style::Flat(badge) => generate_svg::flat_svg(badge, layout, id_suffix)?,
+            Style::FlatSquare(badge) => generate_svg::flat_square_svg(badge, layout, id_suffix)?,
+            Style::Plastic(badge) => generate_svg::plastic_svg(badge, layout, id_suffix)?,
+            Style::ForTheBadge(badge) => generate_svg::for_the_badge_svg(badge, layout, id_suffix)?,
+            Style::Social(badge) => generate_svg::social_svg(badge, layout, id_suffix)?,
         };
 
         Ok(style)
     }
+
+    /// Computes the geometry of the badge in the chosen badge style, without
+    /// generating the SVG.
+    ///
+    /// The returned [BadgeLayout] gives the total size of the badge, along with
+    /// the placement of each side, its text, and the logo. This matches the SVG
+    /// returned by [generate_svg](Style::generate_svg) exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, Style};
+    /// let layout = Style::Flat(Badge::default()).layout().unwrap();
+    /// assert_eq!(layout.height, 20.0);
+    /// assert_eq!(layout.width, layout.label.width + layout.message.width);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// The badge data is verified the same way as in
+    /// [generate_svg](Style::generate_svg), apart from the logo, which is never
+    /// downloaded.
+    ///
+    pub fn layout(&self) -> Result<BadgeLayout, BadgeError> {
+        let layout = self.checked_layout()?;
+        Ok(generate_layout::badge_layout(&layout))
+    }
+
+    /// Reports the WCAG 2.x contrast ratio between the text and background of each
+    /// side of the badge, without generating the SVG.
+    ///
+    /// The colors are reported as they would be written to the SVG, so with
+    /// [ContrastMode::AutoAdjust] they include any adjustment. The report is returned
+    /// even when [ContrastMode::Strict] would fail generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsbadges::{Badge, Style};
+    /// let badge = Badge {
+    ///     msg_color: String::from("yellow"),
+    ///     ..Badge::default()
+    /// };
+    /// let report = Style::Flat(badge).contrast_report().unwrap();
+    /// assert!(report.passes_aa());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Colors are verified as in [generate_svg](Style::generate_svg), so this can return
+    /// [ColorNotValid](BadgeError::ColorNotValid).
+    ///
+    pub fn contrast_report(&self) -> Result<ContrastReport, BadgeError> {
+        let layout = generate_layout::layout(self.badge(), self.metrics())?;
+        let mut sides = layout.segments.into_iter().map(|segment| segment.contrast);
+        Ok(ContrastReport {
+            label: sides.next().flatten(),
+            message: sides.next().flatten(),
+            extra_segments: sides.collect(),
+        })
+    }
+
+    /// Lay out the badge, enforcing its contrast mode.
+    pub(super) fn checked_layout(&self) -> Result<Layout, BadgeError> {
+        let badge = self.badge();
+        let layout = generate_layout::layout(badge, self.metrics())?;
+        if badge.contrast_mode == ContrastMode::Strict {
+            generate_layout::check_contrast(&layout)?;
+        }
+        Ok(layout)
+    }
+
+    /// The badge data held by this Style.
+    pub(super) fn badge(&self) -> &Badge {
+        match self {
+            Style::Flat(badge)
+            | Style::FlatSquare(badge)
+            | Style::Plastic(badge)
+            | Style::ForTheBadge(badge)
+            | Style::Social(badge) => badge,
+        }
+    }
+
+    /// The measurements used to lay out this Style.
+    fn metrics(&self) -> &'static StyleMetrics {
+        match self {
+            Style::Flat(_) | Style::FlatSquare(_) => &style_metrics::FLAT,
+            Style::Plastic(_) => &style_metrics::PLASTIC,
+            Style::ForTheBadge(_) => &style_metrics::FOR_THE_BADGE,
+            Style::Social(_) => &style_metrics::SOCIAL,
+        }
+    }
 }
@@ -0,0 +1,205 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! The measurements that describe each badge style. These are all the layout
+//! engine needs to know about a style; see [generate_layout](super::generate_layout).
+
+use super::format_helper::{uppercase_first_letter, AccentColors, BadgeFont};
+
+/// A change applied to text before it is measured and rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextTransform {
+    /// Leave the text as-is
+    None,
+    /// Make every character uppercase
+    Uppercase,
+    /// Make the first character uppercase
+    CapitalizeFirst,
+}
+
+impl TextTransform {
+    /// Apply this transform to the given text.
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            TextTransform::None => text.to_string(),
+            TextTransform::Uppercase => text.to_uppercase(),
+            TextTransform::CapitalizeFirst => uppercase_first_letter(text),
+        }
+    }
+}
+
+/// Measurements for the text on one side of a badge.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SideMetrics {
+    /// font used to measure the text
+    pub font: BadgeFont,
+    /// size of the font used to measure the text, in px
+    pub font_size: f32,
+    /// change applied to the text before it is measured
+    pub text_transform: TextTransform,
+    /// space added between each grapheme of the text, in px
+    pub letter_spacing: f32,
+    /// padding on either side of the text, in px
+    pub horiz_padding: f32,
+}
+
+/// Measurements for an entire badge style.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StyleMetrics {
+    /// height of the badge, in px
+    pub badge_height: f32,
+    /// placement of the text baseline in y, in px
+    pub text_y: f32,
+    /// measurements for the label (left side)
+    pub label: SideMetrics,
+    /// measurements for the message (right side)
+    pub msg: SideMetrics,
+    /// width and height of the logo, in px
+    pub logo_size: f32,
+    /// space between the logo and the label text, in px
+    pub logo_padding: f32,
+    /// space between the label and message boxes, in px
+    pub gutter: f32,
+    /// width of the outline drawn around the label and message boxes, in px
+    pub outline_width: f32,
+    /// how far text is shifted toward the divider between label and message, in px
+    pub text_nudge: f32,
+    /// keep the label box, padding and all, even when there is no label text
+    pub keep_empty_label: bool,
+    /// drop the message box entirely when there is no message text
+    pub hide_empty_msg: bool,
+    /// colors for styles whose backgrounds don't follow the badge colors
+    pub fixed_colors: Option<FixedColors>,
+}
+
+/// The colors of a style that draws its own backgrounds, regardless of the badge colors.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FixedColors {
+    /// background color of the label (left side)
+    pub label_background: &'static str,
+    /// background color of the message (right side)
+    pub msg_background: &'static str,
+    /// text and shadow colors for both sides
+    pub accent_colors: AccentColors,
+}
+
+/// Text measurements shared by the flat, flat square and plastic styles
+const VERDANA_LIKE: SideMetrics = SideMetrics {
+    font: BadgeFont::PtSansRegular,
+    font_size: 17.0,
+    text_transform: TextTransform::None,
+    letter_spacing: 0.0,
+    horiz_padding: 5.0,
+};
+
+/// The "flat" and "flat square" badge styles
+pub(crate) const FLAT: StyleMetrics = StyleMetrics {
+    badge_height: 20.0,
+    text_y: 14.0,
+    label: VERDANA_LIKE,
+    msg: VERDANA_LIKE,
+    logo_size: 14.0,
+    logo_padding: 3.0,
+    gutter: 0.0,
+    outline_width: 0.0,
+    text_nudge: 1.0,
+    keep_empty_label: false,
+    hide_empty_msg: false,
+    fixed_colors: None,
+};
+
+/// The "plastic" badge style
+pub(crate) const PLASTIC: StyleMetrics = StyleMetrics {
+    badge_height: 18.0,
+    text_y: 13.0,
+    ..FLAT
+};
+
+/// The "for the badge" badge style
+pub(crate) const FOR_THE_BADGE: StyleMetrics = StyleMetrics {
+    badge_height: 28.0,
+    text_y: 17.5,
+    label: SideMetrics {
+        font: BadgeFont::PtSansRegular,
+        font_size: 14.0,
+        text_transform: TextTransform::Uppercase,
+        letter_spacing: 2.0,
+        horiz_padding: 11.0,
+    },
+    msg: SideMetrics {
+        font: BadgeFont::PtSansBold,
+        font_size: 17.0,
+        text_transform: TextTransform::Uppercase,
+        letter_spacing: 1.5,
+        horiz_padding: 11.0,
+    },
+    logo_size: 14.0,
+    logo_padding: 3.0,
+    gutter: 0.0,
+    outline_width: 0.0,
+    text_nudge: 0.0,
+    keep_empty_label: false,
+    hide_empty_msg: false,
+    fixed_colors: None,
+};
+
+/// The "social" badge style
+pub(crate) const SOCIAL: StyleMetrics = StyleMetrics {
+    badge_height: 20.0,
+    text_y: 14.0,
+    label: SideMetrics {
+        font: BadgeFont::OpenSansRegular,
+        font_size: 16.0,
+        text_transform: TextTransform::CapitalizeFirst,
+        letter_spacing: 0.0,
+        horiz_padding: 5.0,
+    },
+    msg: SideMetrics {
+        font: BadgeFont::OpenSansRegular,
+        font_size: 16.0,
+        text_transform: TextTransform::None,
+        letter_spacing: 0.0,
+        horiz_padding: 4.0,
+    },
+    logo_size: 14.0,
+    logo_padding: 3.0,
+    gutter: 6.0,
+    outline_width: 1.0,
+    text_nudge: 0.0,
+    keep_empty_label: true,
+    hide_empty_msg: true,
+    fixed_colors: Some(FixedColors {
+        label_background: "#fcfcfc",
+        msg_background: "#fafafa",
+        accent_colors: AccentColors {
+            text_color: "#333",
+            shadow_color: "#fff",
+        },
+    }),
+};
@@ -0,0 +1,159 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Shaping-based text measurement. This handles kerning, complex scripts, and, with
+//! the `system-fonts` feature, characters that are missing from the bundled fonts.
+
+use rustybuzz::{Face, UnicodeBuffer};
+#[cfg(feature = "system-fonts")]
+use std::collections::HashMap;
+#[cfg(feature = "system-fonts")]
+use std::sync::{Mutex, OnceLock};
+use unicode_normalization::char::is_combining_mark;
+
+/// The font used to shape a run of text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RunFont {
+    /// The bundled font requested by the badge style
+    Primary,
+    /// A system font that covers characters the bundled font does not
+    #[cfg(feature = "system-fonts")]
+    Fallback(fontdb::ID),
+}
+
+/// System fonts, loaded on first use.
+#[cfg(feature = "system-fonts")]
+fn system_fonts() -> &'static fontdb::Database {
+    static SYSTEM_FONTS: OnceLock<fontdb::Database> = OnceLock::new();
+    SYSTEM_FONTS.get_or_init(|| {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        db
+    })
+}
+
+/// Find a system font that has a glyph for this character.
+///
+/// Regular, upright faces are preferred. Results are cached per character, since
+/// finding a match means parsing every face in the database.
+#[cfg(feature = "system-fonts")]
+fn fallback_font(c: char) -> Option<fontdb::ID> {
+    static FALLBACKS: OnceLock<Mutex<HashMap<char, Option<fontdb::ID>>>> = OnceLock::new();
+    let fallbacks = FALLBACKS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(id) = fallbacks.lock().ok().and_then(|f| f.get(&c).copied()) {
+        return id;
+    }
+
+    let db = system_fonts();
+    let mut faces = db.faces().collect::<Vec<_>>();
+    faces.sort_by_key(|f| {
+        (
+            f.style != fontdb::Style::Normal,
+            f.weight.0.abs_diff(fontdb::Weight::NORMAL.0),
+        )
+    });
+    let id = faces.into_iter().map(|f| f.id).find(|id| {
+        db.with_face_data(*id, |data, index| {
+            face_covers(data, index, c).unwrap_or(false)
+        })
+        .unwrap_or(false)
+    });
+
+    if let Ok(mut f) = fallbacks.lock() {
+        f.insert(c, id);
+    }
+    id
+}
+
+/// Check if the font face in this data has a glyph for the character.
+#[cfg(feature = "system-fonts")]
+fn face_covers(data: &[u8], index: u32, c: char) -> Option<bool> {
+    let face = rustybuzz::ttf_parser::Face::parse(data, index).ok()?;
+    Some(face.glyph_index(c).is_some())
+}
+
+/// Characters that never start a new run; they are shaped with whatever precedes them.
+fn extends_run(c: char) -> bool {
+    is_combining_mark(c)
+        || c == '\u{200D}'
+        || ('\u{FE00}'..='\u{FE0F}').contains(&c)
+        || ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+/// Shape text with a single face, and return its advance width at the given font size.
+///
+/// Widths are scaled against the face's full height, the same way rusttype scales
+/// glyphs, so that shaped and unshaped measurements are interchangeable.
+pub(crate) fn face_width(face: &Face, text: &str, font_size: f32) -> f32 {
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    let glyphs = rustybuzz::shape(face, &[], buffer);
+    let advance: i32 = glyphs.glyph_positions().iter().map(|p| p.x_advance).sum();
+    let height = f32::from(face.ascender()) - f32::from(face.descender());
+    advance as f32 * font_size / height
+}
+
+/// Measure text by shaping it, falling back to system fonts for any characters
+/// the primary font can't display, with the `system-fonts` feature.
+///
+/// Text is split into runs that share a font. Each run is shaped on its own, so
+/// kerning, ligatures, and contextual forms (as in Arabic or Devanagari) are taken
+/// into account. Characters that no font covers are measured with the primary font's
+/// missing-glyph box. Without `system-fonts`, that is every character the primary
+/// font lacks, so widths don't depend on the fonts installed on the machine.
+pub(crate) fn shaped_width(primary: &Face, text: &str, font_size: f32) -> f32 {
+    let mut runs: Vec<(RunFont, String)> = Vec::new();
+    for c in text.chars() {
+        let font = match runs.last() {
+            Some((font, _)) if extends_run(c) => *font,
+            _ if primary.glyph_index(c).is_some() => RunFont::Primary,
+            #[cfg(feature = "system-fonts")]
+            _ => fallback_font(c).map_or(RunFont::Primary, RunFont::Fallback),
+            #[cfg(not(feature = "system-fonts"))]
+            _ => RunFont::Primary,
+        };
+        match runs.last_mut() {
+            Some((last_font, run)) if *last_font == font => run.push(c),
+            _ => runs.push((font, c.to_string())),
+        }
+    }
+
+    runs.iter()
+        .map(|(font, run)| match font {
+            RunFont::Primary => face_width(primary, run, font_size),
+            #[cfg(feature = "system-fonts")]
+            RunFont::Fallback(id) => system_fonts()
+                .with_face_data(*id, |data, index| {
+                    Face::from_slice(data, index).map(|face| face_width(&face, run, font_size))
+                })
+                .flatten()
+                .unwrap_or_else(|| face_width(primary, run, font_size)),
+        })
+        .sum()
+}
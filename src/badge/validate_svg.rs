@@ -0,0 +1,172 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Check that a generated badge is well-formed SVG
+
+use super::badge_type::BadgeError;
+use super::format_helper::verify_color;
+use roxmltree::{Document, Node};
+use std::collections::HashSet;
+
+/// The SVG namespace, which every element of a badge must belong to.
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Every element RSBadges places in a badge.
+const KNOWN_ELEMENTS: [&str; 12] = [
+    "a",
+    "clipPath",
+    "g",
+    "image",
+    "linearGradient",
+    "path",
+    "rect",
+    "stop",
+    "style",
+    "svg",
+    "text",
+    "title",
+];
+
+/// Attributes that hold a paint or color.
+const COLOR_ATTRIBUTES: [&str; 3] = ["fill", "stroke", "stop-color"];
+
+/// Attributes that hold a single number, optionally a percentage.
+const NUMBER_ATTRIBUTES: [&str; 14] = [
+    "x",
+    "y",
+    "x1",
+    "y1",
+    "x2",
+    "y2",
+    "width",
+    "height",
+    "rx",
+    "offset",
+    "textLength",
+    "fill-opacity",
+    "stop-opacity",
+    "stroke-opacity",
+];
+
+/// Shorthand for the error returned by every check.
+fn invalid(node: Node, problem: String) -> BadgeError {
+    BadgeError::InvalidSvg(format!("<{}>: {}", node.tag_name().name(), problem))
+}
+
+/// Check that a paint attribute is a color, `none`, or a reference.
+fn check_color(node: Node, attr: &str, value: &str) -> Result<(), BadgeError> {
+    if value == "none" || value.starts_with("url(") || verify_color(value).is_ok() {
+        return Ok(());
+    }
+    Err(invalid(
+        node,
+        format!("{}=\"{}\" is not a valid color", attr, value),
+    ))
+}
+
+/// Check that a numeric attribute holds a finite number.
+fn check_number(node: Node, attr: &str, value: &str) -> Result<(), BadgeError> {
+    match value.trim_end_matches('%').parse::<f32>() {
+        Ok(number) if number.is_finite() => Ok(()),
+        _ => Err(invalid(
+            node,
+            format!("{}=\"{}\" is not a valid number", attr, value),
+        )),
+    }
+}
+
+/// Collect the ids referenced through `url(#id)` in `text`.
+fn url_references(text: &str) -> Vec<&str> {
+    text.match_indices("url(#")
+        .filter_map(|(start, pattern)| {
+            let rest = &text[start + pattern.len()..];
+            rest.find(')').map(|end| rest[..end].trim())
+        })
+        .collect()
+}
+
+/// Check that a generated badge SVG is well-formed and free of known mistakes.
+///
+/// The SVG is parsed as XML, then checked for:
+///
+/// - elements outside of the SVG namespace, or that RSBadges never generates
+/// - color attributes that are not valid CSS colors (e.g. `stroke="d5d5d5"`)
+/// - numeric attributes that are not finite numbers
+/// - duplicate ids, and `url(#id)` references to ids that do not exist
+///
+/// # Errors
+///
+/// Returns [BadgeError::InvalidSvg] describing the first problem found.
+///
+pub fn validate_svg(svg: &str) -> Result<(), BadgeError> {
+    let doc = Document::parse(svg).map_err(|e| BadgeError::InvalidSvg(e.to_string()))?;
+
+    let mut ids = HashSet::new();
+    let mut references = Vec::new();
+    for node in doc.descendants() {
+        if node.is_text() {
+            if node.parent().is_some_and(|p| p.has_tag_name("style")) {
+                references.extend(url_references(node.text().unwrap_or("")));
+            }
+            continue;
+        }
+        if !node.is_element() {
+            continue;
+        }
+
+        let tag = node.tag_name();
+        if tag.namespace() != Some(SVG_NAMESPACE) || !KNOWN_ELEMENTS.contains(&tag.name()) {
+            return Err(invalid(node, String::from("unknown SVG element")));
+        }
+
+        for attr in node.attributes() {
+            let (name, value) = (attr.name(), attr.value());
+            if attr.namespace().is_none() {
+                if COLOR_ATTRIBUTES.contains(&name) {
+                    check_color(node, name, value)?;
+                } else if NUMBER_ATTRIBUTES.contains(&name) {
+                    check_number(node, name, value)?;
+                } else if name == "id" && !ids.insert(value) {
+                    return Err(invalid(node, format!("duplicate id \"{}\"", value)));
+                }
+            }
+            references.extend(url_references(value));
+        }
+    }
+
+    for reference in references {
+        if !ids.contains(reference) {
+            return Err(BadgeError::InvalidSvg(format!(
+                "url(#{}) refers to an element that does not exist",
+                reference
+            )));
+        }
+    }
+    Ok(())
+}
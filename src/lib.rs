@@ -47,7 +47,7 @@
 //!
 //! *Social*
 //!
-//! <svg xmlns="http://www.w3.org/2000/svg"    xmlns:xlink="http://www.w3.org/1999/xlink" width="156.54895" height="20" role="img" aria-label="Style: social">        <style>a:hover #llink{fill:url(#roundzQkNqHB);stroke:#ccc}a:hover #rlink{fill:#4183c4}</style>    <linearGradient id="smoothzQkNqHB" x2="0" y2="100%">        <stop offset="0" stop-color="#fcfcfc" stop-opacity="0"/>        <stop offset="1" stop-opacity=".1"/>    </linearGradient>    <linearGradient id="roundzQkNqHB" x2="0" y2="100%">        <stop offset="0" stop-color="#ccc" stop-opacity=".1"/>        <stop offset="1" stop-opacity=".1"/>    </linearGradient>    <g stroke="#d5d5d5">        <rect stroke="none" fill="#fcfcfc" x="0.5" y="0.5" width="52.226604" height="19" rx="2"/>                <rect x="58.726604" y="0.5" width="97.32234" height="19" rx="2" fill="#fafafa"/>        <rect x="58.226604" y="7.5" width="0.5" height="5" stroke="#fafafa"/>        <path d="M58.726604 6.5 l-3 3v1 l3 3" stroke="d5d5d5" fill="#fafafa"/>            </g>        <image x="5" y="3" width="14" height="14" xlink:href="data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHZpZXdCb3g9IjAgMCAyNCAyNCIgeG1sbnM9Imh0dHA6Ly93d3cudzMub3JnLzIwMDAvc3ZnIj48dGl0bGU+UnVzdDwvdGl0bGU+PHBhdGggZD0iTTIzLjgzNDYgMTEuNzAzM2wtMS4wMDczLS42MjM2YTEzLjcyNjggMTMuNzI2OCAwIDAwLS4wMjgzLS4yOTM2bC44NjU2LS44MDY5YS4zNDgzLjM0ODMgMCAwMC0uMTE1NC0uNTc4bC0xLjEwNjYtLjQxNGE4LjQ5NTggOC40OTU4IDAgMDAtLjA4Ny0uMjg1NmwuNjkwNC0uOTU4N2EuMzQ2Mi4zNDYyIDAgMDAtLjIyNTctLjU0NDZsLTEuMTY2My0uMTg5NGE5LjM1NzQgOS4zNTc0IDAgMDAtLjE0MDctLjI2MjJsLjQ5LTEuMDc2MWEuMzQzNy4zNDM3IDAgMDAtLjAyNzQtLjMzNjEuMzQ4Ni4zNDg2IDAgMDAtLjMwMDYtLjE1NGwtMS4xODQ1LjA0MTZhNi43NDQ0IDYuNzQ0NCAwIDAwLS4xODczLS4yMjY4bC4yNzIzLTEuMTUzYS4zNDcyLjM0NzIgMCAwMC0uNDE3LS40MTcybC0xLjE1MzIuMjcyNGExNC4wMTgzIDE0LjAxODMgMCAwMC0uMjI3OC0uMTg3M2wuMDQxNS0xLjE4NDVhLjM0NDIuMzQ0MiAwIDAwLS40OS0uMzI4bC0xLjA3Ni40OTFjLS4wODcyLS4wNDc2LS4xNzQyLS4wOTUyLS4yNjIzLS4xNDA3bC0uMTkwMy0xLjE2NzNBLjM0ODMuMzQ4MyAwIDAwMTYuMjU2Ljk1NWwtLjk1OTcuNjkwNWE4LjQ4NjcgOC40ODY3IDAgMDAtLjI4NTUtLjA4NmwtLjQxNC0xLjEwNjZhLjM0ODMuMzQ4MyAwIDAwLS41NzgxLS4xMTU0bC0uODA2OS44NjY2YTkuMjkzNiA5LjI5MzYgMCAwMC0uMjkzNi0uMDI4NEwxMi4yOTQ2LjE2ODNhLjM0NjIuMzQ2MiAwIDAwLS41ODkyIDBsLS42MjM2IDEuMDA3M2ExMy43MzgzIDEzLjczODMgMCAwMC0uMjkzNi4wMjg0TDkuOTgwMy4zMzc0YS4zNDYyLjM0NjIgMCAwMC0uNTc4LjExNTRsLS40MTQxIDEuMTA2NWMtLjA5NjIuMDI3NC0uMTkwMy4wNTY3LS4yODU1LjA4Nkw3Ljc0NC45NTVhLjM0ODMuMzQ4MyAwIDAwLS41NDQ3LjIyNThMNy4wMDkgMi4zNDhhOS4zNTc0IDkuMzU3NCAwIDAwLS4yNjIyLjE0MDdsLTEuMDc2Mi0uNDkxYS4zNDYyLjM0NjIgMCAwMC0uNDkuMzI4bC4wNDE2IDEuMTg0NWE3Ljk4MjYgNy45ODI2IDAgMDAtLjIyNzguMTg3M0wzLjg0MTMgMy40MjVhLjM0NzIuMzQ3MiAwIDAwLS40MTcxLjQxNzFsLjI3MTMgMS4xNTMxYy0uMDYyOC4wNzUtLjEyNTUuMTUwOS0uMTg2My4yMjY4bC0xLjE4NDUtLjA0MTVhLjM0NjIuMzQ2MiAwIDAwLS4zMjguNDlsLjQ5MSAxLjA3NjFhOS4xNjcgOS4xNjcgMCAwMC0uMTQwNy4yNjIybC0xLjE2NjIuMTg5NGEuMzQ4My4zNDgzIDAgMDAtLjIyNTguNTQ0NmwuNjkwNC45NTg3YTEzLjMwMyAxMy4zMDMgMCAwMC0uMDg3LjI4NTVsLTEuMTA2NS40MTRhLjM0ODMuMzQ4MyAwIDAwLS4xMTU1LjU3ODFsLjg2NTYuODA3YTkuMjkzNiA5LjI5MzYgMCAwMC0uMDI4My4yOTM1bC0xLjAwNzMuNjIzNmEuMzQ0Mi4zNDQyIDAgMDAwIC41ODkybDEuMDA3My42MjM2Yy4wMDguMDk4Mi4wMTgyLjE5NjQuMDI4My4yOTM2bC0uODY1Ni44MDc5YS4zNDYyLjM0NjIgMCAwMC4xMTU1LjU3OGwxLjEwNjUuNDE0MWMuMDI3My4wOTYyLjA1NjcuMTkxNC4wODcuMjg1NWwtLjY5MDQuOTU4N2EuMzQ1Mi4zNDUyIDAgMDAuMjI2OC41NDQ3bDEuMTY2Mi4xODkzYy4wNDU2LjA4OC4wOTIyLjE3NTEuMTQwOC4yNjIybC0uNDkxIDEuMDc2MmEuMzQ2Mi4zNDYyIDAgMDAuMzI4LjQ5bDEuMTgzNC0uMDQxNWMuMDYxOC4wNzY5LjEyMzUuMTUyOC4xODczLjIyNzdsLS4yNzEzIDEuMTU0MWEuMzQ2Mi4zNDYyIDAgMDAuNDE3MS40MTYxbDEuMTUzLS4yNzEzYy4wNzUuMDYzOC4xNTEuMTI1NS4yMjc5LjE4NjNsLS4wNDE1IDEuMTg0NWEuMzQ0Mi4zNDQyIDAgMDAuNDkuMzI3bDEuMDc2MS0uNDljLjA4Ny4wNDg2LjE3NDEuMDk1MS4yNjIyLjE0MDdsLjE5MDMgMS4xNjYyYS4zNDgzLjM0ODMgMCAwMC41NDQ3LjIyNjhsLjk1ODctLjY5MDRhOS4yOTkgOS4yOTkgMCAwMC4yODU1LjA4N2wuNDE0IDEuMTA2NmEuMzQ1Mi4zNDUyIDAgMDAuNTc4MS4xMTU0bC44MDc5LS44NjU2Yy4wOTcyLjAxMTEuMTk1NC4wMjAzLjI5MzYuMDI5NGwuNjIzNiAxLjAwNzNhLjM0NzIuMzQ3MiAwIDAwLjU4OTIgMGwuNjIzNi0xLjAwNzNjLjA5ODItLjAwOTEuMTk2NC0uMDE4My4yOTM2LS4wMjk0bC44MDY5Ljg2NTZhLjM0ODMuMzQ4MyAwIDAwLjU3OC0uMTE1NGwuNDE0MS0xLjEwNjZhOC40NjI2IDguNDYyNiAwIDAwLjI4NTUtLjA4N2wuOTU4Ny42OTA0YS4zNDUyLjM0NTIgMCAwMC41NDQ3LS4yMjY4bC4xOTAzLTEuMTY2MmMuMDg4LS4wNDU2LjE3NTEtLjA5MzEuMjYyMi0uMTQwN2wxLjA3NjIuNDlhLjM0NzIuMzQ3MiAwIDAwLjQ5LS4zMjdsLS4wNDE1LTEuMTg0NWE2LjcyNjcgNi43MjY3IDAgMDAuMjI2Ny0uMTg2M2wxLjE1MzEuMjcxM2EuMzQ3Mi4zNDcyIDAgMDAuNDE3MS0uNDE2bC0uMjcxMy0xLjE1NDJjLjA2MjgtLjA3NDkuMTI1NS0uMTUwOC4xODYzLS4yMjc4bDEuMTg0NS4wNDE1YS4zNDQyLjM0NDIgMCAwMC4zMjgtLjQ5bC0uNDktMS4wNzZjLjA0NzUtLjA4NzIuMDk1MS0uMTc0Mi4xNDA3LS4yNjIzbDEuMTY2Mi0uMTg5M2EuMzQ4My4zNDgzIDAgMDAuMjI1OC0uNTQ0N2wtLjY5MDQtLjk1ODcuMDg3LS4yODU1IDEuMTA2Ni0uNDE0YS4zNDYyLjM0NjIgMCAwMC4xMTU0LS41NzgxbC0uODY1Ni0uODA3OWMuMDEwMS0uMDk3Mi4wMjAyLS4xOTU0LjAyODMtLjI5MzZsMS4wMDczLS42MjM2YS4zNDQyLjM0NDIgMCAwMDAtLjU4OTJ6bS02Ljc0MTMgOC4zNTUxYS43MTM4LjcxMzggMCAwMS4yOTg2LTEuMzk2LjcxNC43MTQgMCAxMS0uMjk5NyAxLjM5NnptLS4zNDIyLTIuMzE0MmEuNjQ5LjY0OSAwIDAwLS43NzE1LjVsLS4zNTczIDEuNjY4NWMtMS4xMDM1LjUwMS0yLjMyODUuNzc5NS0zLjYxOTMuNzc5NWE4LjczNjggOC43MzY4IDAgMDEtMy42OTUxLS44MTRsLS4zNTc0LTEuNjY4NGEuNjQ4LjY0OCAwIDAwLS43NzE0LS40OTlsLTEuNDczLjMxNThhOC43MjE2IDguNzIxNiAwIDAxLS43NjEzLS44OThoNy4xNjc2Yy4wODEgMCAuMTM1Ni0uMDE0MS4xMzU2LS4wODh2LTIuNTM2YzAtLjA3NC0uMDUzNi0uMDg4MS0uMTM1Ni0uMDg4MWgtMi4wOTY2di0xLjYwNzdoMi4yNjc3Yy4yMDY1IDAgMS4xMDY1LjA1ODcgMS4zOTQgMS4yMDg4LjA5MDEuMzUzMy4yODc1IDEuNTA0NC40MjMyIDEuODcyOS4xMzQ2LjQxMy42ODMzIDEuMjM4MSAxLjI2ODUgMS4yMzgxaDMuNTcxNmEuNzQ5Mi43NDkyIDAgMDAuMTI5Ni0uMDEzMSA4Ljc4NzQgOC43ODc0IDAgMDEtLjgxMTkuOTUyNnpNNi44MzY5IDIwLjAyNGEuNzE0LjcxNCAwIDExLS4yOTk3LTEuMzk2LjcxNC43MTQgMCAwMS4yOTk3IDEuMzk2ek00LjExNzcgOC45OTcyYS43MTM3LjcxMzcgMCAxMS0xLjMwNC41NzkxLjcxMzcuNzEzNyAwIDAxMS4zMDQtLjU3OXptLS44MzUyIDEuOTgxM2wxLjUzNDctLjY4MjRhLjY1LjY1IDAgMDAuMzMtLjg1ODVsLS4zMTU4LS43MTQ3aDEuMjQzMnY1LjYwMjVIMy41NjY5YTguNzc1MyA4Ljc3NTMgMCAwMS0uMjgzNC0zLjM0OHptNi43MzQzLS41NDM3VjguNzgzNmgyLjk2MDFjLjE1MyAwIDEuMDc5Mi4xNzcyIDEuMDc5Mi44Njk3IDAgLjU3NS0uNzEwNy43ODE1LTEuMjk0OC43ODE1em0xMC43NTc0IDEuNDg2MmMwIC4yMTg3LS4wMDguNDM2My0uMDI0My42NTFoLS45Yy0uMDkgMC0uMTI2NS4wNTg2LS4xMjY1LjE0Nzd2LjQxM2MwIC45NzMtLjU0ODcgMS4xODQ2LTEuMDI5NiAxLjIzODItLjQ1NzYuMDUxNy0uOTY0OC0uMTkxMy0xLjAyNzUtLjQ3MTctLjI3MDQtMS41MTg2LS43MTk4LTEuODQzNi0xLjQzMDUtMi40MDM0Ljg4MTctLjU1OTkgMS43OTktMS4zODYgMS43OTktMi40OTE1IDAtMS4xOTM2LS44MTktMS45NDU4LTEuMzc2OS0yLjMxNTMtLjc4MjUtLjUxNjMtMS42NDkxLS42MTk1LTEuODgzLS42MTk1SDUuNDY4MmE4Ljc2NTEgOC43NjUxIDAgMDE0LjkwNy0yLjc2OTlsMS4wOTc0IDEuMTUxYS42NDguNjQ4IDAgMDAuOTE4Mi4wMjEzbDEuMjI3LTEuMTc0M2E4Ljc3NTMgOC43NzUzIDAgMDE2LjAwNDQgNC4yNzYybC0uODQwMyAxLjg5ODJhLjY1Mi42NTIgMCAwMC4zMy44NTg1bDEuNjE3OC43MTg4Yy4wMjgzLjI4NzUuMDQyNS41NzcuMDQyNS44NzE3em0tOS4zMDA2LTkuNTk5M2EuNzEyOC43MTI4IDAgMTEuOTg0IDEuMDMxNi43MTM3LjcxMzcgMCAwMS0uOTg0LTEuMDMxNnptOC4zMzg5IDYuNzFhLjcxMDcuNzEwNyAwIDAxLjkzOTUtLjM2MjUuNzEzNy43MTM3IDAgMTEtLjk0MDUuMzYzNXoiLz48L3N2Zz4="/>        <g aria-hidden="true" fill="#333" text-anchor="middle" font-family="Helvetica Neue,Helvetica,Arial,sans-serif" text-rendering="geometricPrecision" font-weight="700" font-size="110px" line-height="14px">        <rect id="llink" stroke="#d5d5d5" fill="url(#smoothzQkNqHB)" x=".5" y=".5" width="52.226604" height="19" rx="2" />        <text aria-hidden="true" x="346.13306" y="150" fill="#fff" transform="scale(.1)" textLength="252.26605">Rust</text>        <text x="346.13306" y="140" transform="scale(.1)" textLength="252.26605">Rust</text>        <text aria-hidden="true" x="1068.8777" y="150" fill="#fff" transform="scale(.1)" textLength="893.2234">so hot right now</text>        <text id="rlink" x="1068.8777" y="140" transform="scale(.1)" textLength="893.2234">so hot right now</text>    </g>                </svg>
+//! <svg xmlns="http://www.w3.org/2000/svg"    xmlns:xlink="http://www.w3.org/1999/xlink" width="156.54895" height="20" role="img" aria-label="Style: social">        <style>a:hover #llinkzQkNqHB{fill:url(#roundzQkNqHB);stroke:#ccc}a:hover #rlinkzQkNqHB{fill:#4183c4}</style>    <linearGradient id="smoothzQkNqHB" x2="0" y2="100%">        <stop offset="0" stop-color="#fcfcfc" stop-opacity="0"/>        <stop offset="1" stop-opacity=".1"/>    </linearGradient>    <linearGradient id="roundzQkNqHB" x2="0" y2="100%">        <stop offset="0" stop-color="#ccc" stop-opacity=".1"/>        <stop offset="1" stop-opacity=".1"/>    </linearGradient>    <g stroke="#d5d5d5">        <rect stroke="none" fill="#fcfcfc" x="0.5" y="0.5" width="52.226604" height="19" rx="2"/>                <rect x="58.726604" y="0.5" width="97.32234" height="19" rx="2" fill="#fafafa"/>        <rect x="58.226604" y="7.5" width="0.5" height="5" stroke="#fafafa"/>        <path d="M58.726604 6.5 l-3 3v1 l3 3" stroke="#d5d5d5" fill="#fafafa"/>            </g>        <image x="5" y="3" width="14" height="14" xlink:href="data:image/svg+xml;base64,PHN2ZyByb2xlPSJpbWciIHZpZXdCb3g9IjAgMCAyNCAyNCIgeG1sbnM9Imh0dHA6Ly93d3cudzMub3JnLzIwMDAvc3ZnIj48dGl0bGU+UnVzdDwvdGl0bGU+PHBhdGggZD0iTTIzLjgzNDYgMTEuNzAzM2wtMS4wMDczLS42MjM2YTEzLjcyNjggMTMuNzI2OCAwIDAwLS4wMjgzLS4yOTM2bC44NjU2LS44MDY5YS4zNDgzLjM0ODMgMCAwMC0uMTE1NC0uNTc4bC0xLjEwNjYtLjQxNGE4LjQ5NTggOC40OTU4IDAgMDAtLjA4Ny0uMjg1NmwuNjkwNC0uOTU4N2EuMzQ2Mi4zNDYyIDAgMDAtLjIyNTctLjU0NDZsLTEuMTY2My0uMTg5NGE5LjM1NzQgOS4zNTc0IDAgMDAtLjE0MDctLjI2MjJsLjQ5LTEuMDc2MWEuMzQzNy4zNDM3IDAgMDAtLjAyNzQtLjMzNjEuMzQ4Ni4zNDg2IDAgMDAtLjMwMDYtLjE1NGwtMS4xODQ1LjA0MTZhNi43NDQ0IDYuNzQ0NCAwIDAwLS4xODczLS4yMjY4bC4yNzIzLTEuMTUzYS4zNDcyLjM0NzIgMCAwMC0uNDE3LS40MTcybC0xLjE1MzIuMjcyNGExNC4wMTgzIDE0LjAxODMgMCAwMC0uMjI3OC0uMTg3M2wuMDQxNS0xLjE4NDVhLjM0NDIuMzQ0MiAwIDAwLS40OS0uMzI4bC0xLjA3Ni40OTFjLS4wODcyLS4wNDc2LS4xNzQyLS4wOTUyLS4yNjIzLS4xNDA3bC0uMTkwMy0xLjE2NzNBLjM0ODMuMzQ4MyAwIDAwMTYuMjU2Ljk1NWwtLjk1OTcuNjkwNWE4LjQ4NjcgOC40ODY3IDAgMDAtLjI4NTUtLjA4NmwtLjQxNC0xLjEwNjZhLjM0ODMuMzQ4MyAwIDAwLS41NzgxLS4xMTU0bC0uODA2OS44NjY2YTkuMjkzNiA5LjI5MzYgMCAwMC0uMjkzNi0uMDI4NEwxMi4yOTQ2LjE2ODNhLjM0NjIuMzQ2MiAwIDAwLS41ODkyIDBsLS42MjM2IDEuMDA3M2ExMy43MzgzIDEzLjczODMgMCAwMC0uMjkzNi4wMjg0TDkuOTgwMy4zMzc0YS4zNDYyLjM0NjIgMCAwMC0uNTc4LjExNTRsLS40MTQxIDEuMTA2NWMtLjA5NjIuMDI3NC0uMTkwMy4wNTY3LS4yODU1LjA4Nkw3Ljc0NC45NTVhLjM0ODMuMzQ4MyAwIDAwLS41NDQ3LjIyNThMNy4wMDkgMi4zNDhhOS4zNTc0IDkuMzU3NCAwIDAwLS4yNjIyLjE0MDdsLTEuMDc2Mi0uNDkxYS4zNDYyLjM0NjIgMCAwMC0uNDkuMzI4bC4wNDE2IDEuMTg0NWE3Ljk4MjYgNy45ODI2IDAgMDAtLjIyNzguMTg3M0wzLjg0MTMgMy40MjVhLjM0NzIuMzQ3MiAwIDAwLS40MTcxLjQxNzFsLjI3MTMgMS4xNTMxYy0uMDYyOC4wNzUtLjEyNTUuMTUwOS0uMTg2My4yMjY4bC0xLjE4NDUtLjA0MTVhLjM0NjIuMzQ2MiAwIDAwLS4zMjguNDlsLjQ5MSAxLjA3NjFhOS4xNjcgOS4xNjcgMCAwMC0uMTQwNy4yNjIybC0xLjE2NjIuMTg5NGEuMzQ4My4zNDgzIDAgMDAtLjIyNTguNTQ0NmwuNjkwNC45NTg3YTEzLjMwMyAxMy4zMDMgMCAwMC0uMDg3LjI4NTVsLTEuMTA2NS40MTRhLjM0ODMuMzQ4MyAwIDAwLS4xMTU1LjU3ODFsLjg2NTYuODA3YTkuMjkzNiA5LjI5MzYgMCAwMC0uMDI4My4yOTM1bC0xLjAwNzMuNjIzNmEuMzQ0Mi4zNDQyIDAgMDAwIC41ODkybDEuMDA3My42MjM2Yy4wMDguMDk4Mi4wMTgyLjE5NjQuMDI4My4yOTM2bC0uODY1Ni44MDc5YS4zNDYyLjM0NjIgMCAwMC4xMTU1LjU3OGwxLjEwNjUuNDE0MWMuMDI3My4wOTYyLjA1NjcuMTkxNC4wODcuMjg1NWwtLjY5MDQuOTU4N2EuMzQ1Mi4zNDUyIDAgMDAuMjI2OC41NDQ3bDEuMTY2Mi4xODkzYy4wNDU2LjA4OC4wOTIyLjE3NTEuMTQwOC4yNjIybC0uNDkxIDEuMDc2MmEuMzQ2Mi4zNDYyIDAgMDAuMzI4LjQ5bDEuMTgzNC0uMDQxNWMuMDYxOC4wNzY5LjEyMzUuMTUyOC4xODczLjIyNzdsLS4yNzEzIDEuMTU0MWEuMzQ2Mi4zNDYyIDAgMDAuNDE3MS40MTYxbDEuMTUzLS4yNzEzYy4wNzUuMDYzOC4xNTEuMTI1NS4yMjc5LjE4NjNsLS4wNDE1IDEuMTg0NWEuMzQ0Mi4zNDQyIDAgMDAuNDkuMzI3bDEuMDc2MS0uNDljLjA4Ny4wNDg2LjE3NDEuMDk1MS4yNjIyLjE0MDdsLjE5MDMgMS4xNjYyYS4zNDgzLjM0ODMgMCAwMC41NDQ3LjIyNjhsLjk1ODctLjY5MDRhOS4yOTkgOS4yOTkgMCAwMC4yODU1LjA4N2wuNDE0IDEuMTA2NmEuMzQ1Mi4zNDUyIDAgMDAuNTc4MS4xMTU0bC44MDc5LS44NjU2Yy4wOTcyLjAxMTEuMTk1NC4wMjAzLjI5MzYuMDI5NGwuNjIzNiAxLjAwNzNhLjM0NzIuMzQ3MiAwIDAwLjU4OTIgMGwuNjIzNi0xLjAwNzNjLjA5ODItLjAwOTEuMTk2NC0uMDE4My4yOTM2LS4wMjk0bC44MDY5Ljg2NTZhLjM0ODMuMzQ4MyAwIDAwLjU3OC0uMTE1NGwuNDE0MS0xLjEwNjZhOC40NjI2IDguNDYyNiAwIDAwLjI4NTUtLjA4N2wuOTU4Ny42OTA0YS4zNDUyLjM0NTIgMCAwMC41NDQ3LS4yMjY4bC4xOTAzLTEuMTY2MmMuMDg4LS4wNDU2LjE3NTEtLjA5MzEuMjYyMi0uMTQwN2wxLjA3NjIuNDlhLjM0NzIuMzQ3MiAwIDAwLjQ5LS4zMjdsLS4wNDE1LTEuMTg0NWE2LjcyNjcgNi43MjY3IDAgMDAuMjI2Ny0uMTg2M2wxLjE1MzEuMjcxM2EuMzQ3Mi4zNDcyIDAgMDAuNDE3MS0uNDE2bC0uMjcxMy0xLjE1NDJjLjA2MjgtLjA3NDkuMTI1NS0uMTUwOC4xODYzLS4yMjc4bDEuMTg0NS4wNDE1YS4zNDQyLjM0NDIgMCAwMC4zMjgtLjQ5bC0uNDktMS4wNzZjLjA0NzUtLjA4NzIuMDk1MS0uMTc0Mi4xNDA3LS4yNjIzbDEuMTY2Mi0uMTg5M2EuMzQ4My4zNDgzIDAgMDAuMjI1OC0uNTQ0N2wtLjY5MDQtLjk1ODcuMDg3LS4yODU1IDEuMTA2Ni0uNDE0YS4zNDYyLjM0NjIgMCAwMC4xMTU0LS41NzgxbC0uODY1Ni0uODA3OWMuMDEwMS0uMDk3Mi4wMjAyLS4xOTU0LjAyODMtLjI5MzZsMS4wMDczLS42MjM2YS4zNDQyLjM0NDIgMCAwMDAtLjU4OTJ6bS02Ljc0MTMgOC4zNTUxYS43MTM4LjcxMzggMCAwMS4yOTg2LTEuMzk2LjcxNC43MTQgMCAxMS0uMjk5NyAxLjM5NnptLS4zNDIyLTIuMzE0MmEuNjQ5LjY0OSAwIDAwLS43NzE1LjVsLS4zNTczIDEuNjY4NWMtMS4xMDM1LjUwMS0yLjMyODUuNzc5NS0zLjYxOTMuNzc5NWE4LjczNjggOC43MzY4IDAgMDEtMy42OTUxLS44MTRsLS4zNTc0LTEuNjY4NGEuNjQ4LjY0OCAwIDAwLS43NzE0LS40OTlsLTEuNDczLjMxNThhOC43MjE2IDguNzIxNiAwIDAxLS43NjEzLS44OThoNy4xNjc2Yy4wODEgMCAuMTM1Ni0uMDE0MS4xMzU2LS4wODh2LTIuNTM2YzAtLjA3NC0uMDUzNi0uMDg4MS0uMTM1Ni0uMDg4MWgtMi4wOTY2di0xLjYwNzdoMi4yNjc3Yy4yMDY1IDAgMS4xMDY1LjA1ODcgMS4zOTQgMS4yMDg4LjA5MDEuMzUzMy4yODc1IDEuNTA0NC40MjMyIDEuODcyOS4xMzQ2LjQxMy42ODMzIDEuMjM4MSAxLjI2ODUgMS4yMzgxaDMuNTcxNmEuNzQ5Mi43NDkyIDAgMDAuMTI5Ni0uMDEzMSA4Ljc4NzQgOC43ODc0IDAgMDEtLjgxMTkuOTUyNnpNNi44MzY5IDIwLjAyNGEuNzE0LjcxNCAwIDExLS4yOTk3LTEuMzk2LjcxNC43MTQgMCAwMS4yOTk3IDEuMzk2ek00LjExNzcgOC45OTcyYS43MTM3LjcxMzcgMCAxMS0xLjMwNC41NzkxLjcxMzcuNzEzNyAwIDAxMS4zMDQtLjU3OXptLS44MzUyIDEuOTgxM2wxLjUzNDctLjY4MjRhLjY1LjY1IDAgMDAuMzMtLjg1ODVsLS4zMTU4LS43MTQ3aDEuMjQzMnY1LjYwMjVIMy41NjY5YTguNzc1MyA4Ljc3NTMgMCAwMS0uMjgzNC0zLjM0OHptNi43MzQzLS41NDM3VjguNzgzNmgyLjk2MDFjLjE1MyAwIDEuMDc5Mi4xNzcyIDEuMDc5Mi44Njk3IDAgLjU3NS0uNzEwNy43ODE1LTEuMjk0OC43ODE1em0xMC43NTc0IDEuNDg2MmMwIC4yMTg3LS4wMDguNDM2My0uMDI0My42NTFoLS45Yy0uMDkgMC0uMTI2NS4wNTg2LS4xMjY1LjE0Nzd2LjQxM2MwIC45NzMtLjU0ODcgMS4xODQ2LTEuMDI5NiAxLjIzODItLjQ1NzYuMDUxNy0uOTY0OC0uMTkxMy0xLjAyNzUtLjQ3MTctLjI3MDQtMS41MTg2LS43MTk4LTEuODQzNi0xLjQzMDUtMi40MDM0Ljg4MTctLjU1OTkgMS43OTktMS4zODYgMS43OTktMi40OTE1IDAtMS4xOTM2LS44MTktMS45NDU4LTEuMzc2OS0yLjMxNTMtLjc4MjUtLjUxNjMtMS42NDkxLS42MTk1LTEuODgzLS42MTk1SDUuNDY4MmE4Ljc2NTEgOC43NjUxIDAgMDE0LjkwNy0yLjc2OTlsMS4wOTc0IDEuMTUxYS42NDguNjQ4IDAgMDAuOTE4Mi4wMjEzbDEuMjI3LTEuMTc0M2E4Ljc3NTMgOC43NzUzIDAgMDE2LjAwNDQgNC4yNzYybC0uODQwMyAxLjg5ODJhLjY1Mi42NTIgMCAwMC4zMy44NTg1bDEuNjE3OC43MTg4Yy4wMjgzLjI4NzUuMDQyNS41NzcuMDQyNS44NzE3em0tOS4zMDA2LTkuNTk5M2EuNzEyOC43MTI4IDAgMTEuOTg0IDEuMDMxNi43MTM3LjcxMzcgMCAwMS0uOTg0LTEuMDMxNnptOC4zMzg5IDYuNzFhLjcxMDcuNzEwNyAwIDAxLjkzOTUtLjM2MjUuNzEzNy43MTM3IDAgMTEtLjk0MDUuMzYzNXoiLz48L3N2Zz4="/>        <g aria-hidden="true" fill="#333" text-anchor="middle" font-family="Helvetica Neue,Helvetica,Arial,sans-serif" text-rendering="geometricPrecision" font-weight="700" font-size="110px" line-height="14px">        <rect id="llinkzQkNqHB" stroke="#d5d5d5" fill="url(#smoothzQkNqHB)" x=".5" y=".5" width="52.226604" height="19" rx="2" />        <text aria-hidden="true" x="346.13306" y="150" fill="#fff" transform="scale(.1)" textLength="252.26605">Rust</text>        <text x="346.13306" y="140" transform="scale(.1)" textLength="252.26605">Rust</text>        <text aria-hidden="true" x="1068.8777" y="150" fill="#fff" transform="scale(.1)" textLength="893.2234">so hot right now</text>        <text id="rlinkzQkNqHB" x="1068.8777" y="140" transform="scale(.1)" textLength="893.2234">so hot right now</text>    </g>                </svg>
 //!
 //! ------------
 //!
@@ -117,6 +117,24 @@
 //!
 //! See the [Badge] and [Style] documentation for more.
 //!
+//! # Features
+//!
+//! - `shaping` (default): measure badge text by shaping it, which accounts for kerning and
+//!   complex scripts like Arabic or Devanagari. Characters missing from the bundled fonts
+//!   are measured with the missing-glyph box of the bundled font.
+//! - `system-fonts`: with `shaping`, measure characters missing from the bundled fonts
+//!   with an installed system font that has them. Badge widths then depend on the fonts
+//!   installed on the machine generating them.
+//! - `glyph-metrics`: measure badge text one glyph at a time using only the bundled
+//!   fonts. Builds without `shaping` need this feature instead.
+//! - `validate` (default): provides `validate_svg()`, which parses a generated badge as XML
+//!   and checks it for invalid colors, numbers, and references.
+//! - `reports` (default): provides [Coverage], [TestResults], [CrateMetadata] and [GitMetadata],
+//!   which read line coverage from lcov, Cobertura and cargo-tarpaulin reports, test
+//!   results from JUnit XML and libtest JSON reports, crate metadata from Cargo.toml and
+//!   Cargo.lock, and repository metadata from a local git checkout.
+//! - `rustls` (default) / `native-tls`: the TLS implementation used to download logos.
+//!
 //! # CLI
 //!
 //! The CLI features all of the customization options from the API, along with a
@@ -142,6 +160,112 @@
 //! | `-o`       | `--open-in-browser`                                       | false
 //! | `-h`       | `--help`                                                  | false
 //! | `-e`       | `--embed-logo`                                            | false
+//! | `-q`       | `--quiet`                                                 | false
+//! | `-v`       | `--verbose`                                               | false
+//!
+//! Commands generate the message of the badge from a report, and take the same options.
+//! `rsbadges coverage <report>` shows the line coverage of an lcov, Cobertura XML or
+//! cargo-tarpaulin JSON report, colored by `--scale`:
+//!
+//! ```sh
+//! rsbadges coverage target/lcov.info --style flatsquare --save-to-svg-at coverage.svg
+//! ```
+//!
+//! `rsbadges tests <report>` shows the results of a JUnit XML report, or of the JSON
+//! output of `cargo test -- -Z unstable-options --format json`, such as "412 passed,
+//! 3 failed". The badge is red if any test failed and green otherwise, and
+//! `--show-skipped` adds the number of skipped tests.
+//!
+//! `rsbadges crate <Cargo.toml> <field>` shows the version, license, msrv, edition or
+//! dependencies of a crate, read from its manifest and Cargo.lock without network
+//! access. Values inherited from the workspace are filled in, and `--package` picks a
+//! member of a workspace:
+//!
+//! ```sh
+//! rsbadges crate Cargo.toml msrv --package my-crate --save-to-svg-at msrv.svg
+//! ```
+//!
+//! `rsbadges git <repository> <field>` shows the tag, commits-since-tag, last-commit,
+//! branch or contributors of a local git repository. It runs `git` and never fetches,
+//! so the badge is as recent as the checkout:
+//!
+//! ```sh
+//! rsbadges git . commits-since-tag --save-to-svg-at unreleased.svg
+//! ```
+//!
+//! `rsbadges grid <badges-file>` lays out several badges in one SVG, such as a row of
+//! badges for the top of a README. Each line of the file lists the options of one
+//! badge, and lines starting with `#` are skipped. Options for the whole run, such as
+//! `--save-to-svg-at`, `--output` or `--quiet`, go on the command line instead.
+//! `--columns` wraps the badges into a grid, `--column-gap` and `--row-gap` space them
+//! out, and `--align` and `--vertical-align` place them in their cells:
+//!
+//! ```sh
+//! $ cat badges.txt
+//! --label build --msg passing --msg-color green --msg-link https://ci.example.com
+//! coverage target/lcov.info
+//! crate Cargo.toml msrv --style flatsquare
+//! $ rsbadges grid badges.txt --column-gap 6 --save-to-svg-at badges.svg
+//! ```
+//!
+//! `--snippet <markdown,html,rst,asciidoc>` prints markup that shows the saved badge,
+//! ready to paste into a README. The alt text comes from `--badge-title`, or the label
+//! and message, and the link from `--badge-link`. `--snippet-url` sets where the image
+//! is, if not at `--save-to-svg-at`:
+//!
+//! ```sh
+//! $ rsbadges --label build --msg passing --badge-link https://ci.example.com \
+//!     --save-to-svg-at build.svg --snippet markdown
+//! [![build: passing](build.svg)](https://ci.example.com)
+//! ```
+//!
+//! `rsbadges readme-update <README.md> <badges-file>` saves each badge of a badge file,
+//! like the one of `grid`, and rewrites the block of the README between
+//! `<!-- rsbadges:start -->` and `<!-- rsbadges:end -->` with a snippet for each, in
+//! Markdown unless `--snippet` says otherwise. Each badge needs `--save-to-svg-at`, and
+//! `--snippet-url` if the README finds it elsewhere. Running it again changes nothing
+//! unless a badge changed, and with `--check` it changes nothing and fails if the
+//! README or a badge is out of date, for use in CI:
+//!
+//! ```sh
+//! rsbadges readme-update README.md badges.txt --check
+//! ```
+//!
+//! Only the badge goes to stdout, so the CLI can be piped into a file: the SVG, unless
+//! it was saved with `--save-to-svg-at` or opened with `--open-in-browser`, or the
+//! `--snippet` if one was asked for. Everything else goes to stderr, where `--quiet`
+//! leaves only errors and `--verbose` adds what was generated and checked. `--output
+//! svg` writes the SVG to stdout even when it is saved, and `--output data-uri` or
+//! `data-uri-urlencoded` write it as a data URI, to inline in HTML or Markdown without
+//! an image file. A `--snippet` then shows the data URI, unless `--snippet-url` is
+//! given:
+//!
+//! ```sh
+//! rsbadges --label build --msg passing --output svg > build.svg
+//! rsbadges --label build --msg passing --output data-uri --snippet html
+//! ```
+//!
+//! The exit code tells what went wrong, by [BadgeError] variant:
+//!
+//! | Code | Error
+//! | ---- | -----
+//! | 0    | None
+//! | 1    | `OutOfDate`, when `readme-update --check` finds stale badges
+//! | 2    | `BadCommandLineArgs`
+//! | 3    | `InvalidStyle`
+//! | 4    | `ColorNotValid`
+//! | 5    | `InvalidScale`
+//! | 6    | `InsufficientContrast`
+//! | 7    | `CannotReadFile`
+//! | 8    | `CannotSaveToFile`
+//! | 9    | `CannotEmbedLogo`
+//! | 10   | `CannotLoadFont`
+//! | 11   | `InvalidSvg`
+//! | 12   | `InvalidReport`
+//! | 13   | `InvalidManifest`
+//! | 14   | `InvalidRepository`
+//! | 15   | `InvalidReadme`
+//! | 16   | `InvalidIdSuffix`
 //!
 //! Run the CLI with the `-h` flag to see all possible arguments and flags.
 //!
@@ -149,8 +273,23 @@
 #![warn(missing_docs)] // warn if there are missing docs
 
 mod badge;
+mod readme;
+#[cfg(feature = "reports")]
+mod report;
 
-pub use badge::{Badge, BadgeError, Style};
+#[cfg(feature = "validate")]
+pub use badge::validate_svg;
+pub use badge::{
+    svg_data_uri, Alignment, Badge, BadgeError, BadgeGrid, BadgeLayout, ColorScale, ContrastMode,
+    ContrastReport, DataUriEncoding, Rect, Segment, SegmentBox, SideContrast, SnippetFormat, Style,
+    TextBox,
+};
+pub use readme::{update_readme_block, README_END, README_START};
+#[cfg(feature = "reports")]
+pub use report::{
+    Coverage, CoverageFormat, CrateField, CrateMetadata, GitField, GitMetadata, TestReportFormat,
+    TestResults,
+};
 use std::fs;
 use std::path::Path;
 
@@ -162,9 +301,6 @@ use std::path::Path;
 /// is malformed or cannot be accessed.
 pub fn save_svg(filepath: &str, svg: &str) -> Result<(), BadgeError> {
     let svg_path = Path::new(filepath);
-    if let Err(c) = fs::write(svg_path, svg) {
-        println!("Error: {}", c);
-        return Err(BadgeError::CannotSaveToFile(String::from(filepath)));
-    }
-    Ok(())
+    fs::write(svg_path, svg)
+        .map_err(|e| BadgeError::CannotSaveToFile(format!("{}: {}", filepath, e)))
 }
@@ -52,72 +52,757 @@
 //! | `-o`       | `--open-in-browser`                                       | false
 //! | `-h`       | `--help`                                                  | false
 //! | `-e`       | `--embed-logo`                                            | false
+//! | `-q`       | `--quiet`                                                 | false
+//! | `-v`       | `--verbose`                                               | false
+//!
+//! Commands generate the message of the badge from a report, and take the same options.
+//! `rsbadges coverage <report>` shows the line coverage of an lcov, Cobertura XML or
+//! cargo-tarpaulin JSON report, colored by `--scale`:
+//!
+//! ```sh
+//! rsbadges coverage target/lcov.info --style flatsquare --save-to-svg-at coverage.svg
+//! ```
+//!
+//! `rsbadges tests <report>` shows the results of a JUnit XML report, or of the JSON
+//! output of `cargo test -- -Z unstable-options --format json`, such as "412 passed,
+//! 3 failed". The badge is red if any test failed and green otherwise, and
+//! `--show-skipped` adds the number of skipped tests.
+//!
+//! `rsbadges crate <Cargo.toml> <field>` shows the version, license, msrv, edition or
+//! dependencies of a crate, read from its manifest and Cargo.lock without network
+//! access. Values inherited from the workspace are filled in, and `--package` picks a
+//! member of a workspace:
+//!
+//! ```sh
+//! rsbadges crate Cargo.toml msrv --package my-crate --save-to-svg-at msrv.svg
+//! ```
+//!
+//! `rsbadges git <repository> <field>` shows the tag, commits-since-tag, last-commit,
+//! branch or contributors of a local git repository. It runs `git` and never fetches,
+//! so the badge is as recent as the checkout:
+//!
+//! ```sh
+//! rsbadges git . commits-since-tag --save-to-svg-at unreleased.svg
+//! ```
+//!
+//! `rsbadges grid <badges-file>` lays out several badges in one SVG, such as a row of
+//! badges for the top of a README. Each line of the file lists the options of one
+//! badge, and lines starting with `#` are skipped. Options for the whole run, such as
+//! `--save-to-svg-at`, `--output` or `--quiet`, go on the command line instead.
+//! `--columns` wraps the badges into a grid, `--column-gap` and `--row-gap` space them
+//! out, and `--align` and `--vertical-align` place them in their cells:
+//!
+//! ```sh
+//! $ cat badges.txt
+//! --label build --msg passing --msg-color green --msg-link https://ci.example.com
+//! coverage target/lcov.info
+//! crate Cargo.toml msrv --style flatsquare
+//! $ rsbadges grid badges.txt --column-gap 6 --save-to-svg-at badges.svg
+//! ```
+//!
+//! `--snippet <markdown,html,rst,asciidoc>` prints markup that shows the saved badge,
+//! ready to paste into a README. The alt text comes from `--badge-title`, or the label
+//! and message, and the link from `--badge-link`. `--snippet-url` sets where the image
+//! is, if not at `--save-to-svg-at`:
+//!
+//! ```sh
+//! $ rsbadges --label build --msg passing --badge-link https://ci.example.com \
+//!     --save-to-svg-at build.svg --snippet markdown
+//! [![build: passing](build.svg)](https://ci.example.com)
+//! ```
+//!
+//! `rsbadges readme-update <README.md> <badges-file>` saves each badge of a badge file,
+//! like the one of `grid`, and rewrites the block of the README between
+//! `<!-- rsbadges:start -->` and `<!-- rsbadges:end -->` with a snippet for each, in
+//! Markdown unless `--snippet` says otherwise. Each badge needs `--save-to-svg-at`, and
+//! `--snippet-url` if the README finds it elsewhere. Running it again changes nothing
+//! unless a badge changed, and with `--check` it changes nothing and fails if the
+//! README or a badge is out of date, for use in CI:
+//!
+//! ```sh
+//! rsbadges readme-update README.md badges.txt --check
+//! ```
+//!
+//! Only the badge goes to stdout, so the CLI can be piped into a file: the SVG, unless
+//! it was saved with `--save-to-svg-at` or opened with `--open-in-browser`, or the
+//! `--snippet` if one was asked for. Everything else goes to stderr, where `--quiet`
+//! leaves only errors and `--verbose` adds what was generated and checked. `--output
+//! svg` writes the SVG to stdout even when it is saved, and `--output data-uri` or
+//! `data-uri-urlencoded` write it as a data URI, to inline in HTML or Markdown without
+//! an image file. A `--snippet` then shows the data URI, unless `--snippet-url` is
+//! given:
+//!
+//! ```sh
+//! rsbadges --label build --msg passing --output svg > build.svg
+//! rsbadges --label build --msg passing --output data-uri --snippet html
+//! ```
+//!
+//! The exit code tells what went wrong, by [BadgeError] variant:
+//!
+//! | Code | Error
+//! | ---- | -----
+//! | 0    | None
+//! | 1    | `OutOfDate`, when `readme-update --check` finds stale badges
+//! | 2    | `BadCommandLineArgs`
+//! | 3    | `InvalidStyle`
+//! | 4    | `ColorNotValid`
+//! | 5    | `InvalidScale`
+//! | 6    | `InsufficientContrast`
+//! | 7    | `CannotReadFile`
+//! | 8    | `CannotSaveToFile`
+//! | 9    | `CannotEmbedLogo`
+//! | 10   | `CannotLoadFont`
+//! | 11   | `InvalidSvg`
+//! | 12   | `InvalidReport`
+//! | 13   | `InvalidManifest`
+//! | 14   | `InvalidRepository`
+//! | 15   | `InvalidReadme`
+//! | 16   | `InvalidIdSuffix`
 //!
 //! Run the CLI with the `-h` flag to see all possible arguments and flags.
 //!
 
 use getopts::Options;
-use rsbadges::{Badge, BadgeError, Style};
+use rsbadges::{
+    Alignment, Badge, BadgeError, BadgeGrid, ColorScale, ContrastMode, DataUriEncoding, Segment,
+    SnippetFormat, Style,
+};
 use std::env;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+/// The commands that generate a badge from a report, for the usage and error messages
+const COMMANDS: &str = "coverage <report>, tests <report>, crate <Cargo.toml> <field>, \
+    git <repository> <field>, grid <badges-file>, readme-update <README.md> <badges-file>";
+
+/// The options that only apply to the grid command
+const GRID_OPTIONS: [&str; 5] = [
+    "columns",
+    "column-gap",
+    "row-gap",
+    "align",
+    "vertical-align",
+];
+
+/// The options that apply to a whole run, and can't be given to a badge of a badge
+/// file, unless the command reading the file allows them
+const RUN_OPTIONS: [&str; 16] = [
+    "help",
+    "quiet",
+    "verbose",
+    "output",
+    "save-to-svg-at",
+    "open-in-browser",
+    "dark-pair",
+    "validate",
+    "check",
+    "snippet",
+    "snippet-url",
+    "columns",
+    "column-gap",
+    "row-gap",
+    "align",
+    "vertical-align",
+];
+
+/// The options that describe a single badge
+const BADGE_OPTIONS: [&str; 34] = [
+    "label",
+    "label-color",
+    "label-link",
+    "msg",
+    "msg-color",
+    "msg-link",
+    "value",
+    "scale",
+    "package",
+    "show-skipped",
+    "segment",
+    "segment-color",
+    "segment-link",
+    "segment-title",
+    "logo",
+    "style",
+    "logo-as-label",
+    "embed-logo",
+    "badge-link",
+    "badge-title",
+    "label-title",
+    "msg-title",
+    "aria-label",
+    "label-text-color",
+    "msg-text-color",
+    "label-shadow-color",
+    "msg-shadow-color",
+    "hide-shadows",
+    "contrast",
+    "page-color",
+    "dark-label-color",
+    "dark-msg-color",
+    "dark-label-text-color",
+    "dark-msg-text-color",
+];
+
+/// The options that the readme-update command takes itself, rather than from its
+/// badge file
+const README_UPDATE_OPTIONS: [&str; 4] = ["quiet", "verbose", "snippet", "check"];
+
+/// What the CLI was asked to do
+enum Run {
+    /// Generate a badge or a grid of badges
+    Generate(RSBadgesOptions),
+    /// Regenerate the badges of a badge file, and the badge block of a README
+    UpdateReadme(ReadmeUpdate),
+    /// Print the usage to stdout, as asked with --help
+    Help(String),
+    /// Print the usage to stderr and fail, as no arguments were given
+    Usage(String),
+}
+
+/// The badges and README of the readme-update command
+struct ReadmeUpdate {
+    /// The README holding the badge block
+    readme: String,
+    /// The badges to list in the block, in order
+    badges: Vec<ReadmeBadge>,
+    /// The markup of the block
+    format: SnippetFormat,
+    /// Only report whether the README and badges are out of date
+    check: bool,
+    /// How much to write to stderr
+    verbosity: Verbosity,
+}
+
+/// One badge of the readme-update command
+struct ReadmeBadge {
+    /// The requested badge
+    style: Style,
+    /// Save the badge to this path
+    save_to_path: String,
+    /// Where the README finds the badge
+    url: String,
+}
+
+/// What the CLI generates
+enum Image {
+    /// A single badge
+    Badge(Box<Style>),
+    /// Several badges in one SVG
+    Grid(BadgeGrid),
+}
+
+/// What the CLI writes to stdout
+#[derive(PartialEq)]
+enum Output {
+    /// The generated SVG, unless it was saved or opened in a browser
+    Auto,
+    /// The generated SVG, even if it was saved
+    Svg,
+    /// The generated SVG as a data URI
+    DataUri(DataUriEncoding),
+}
+
+/// How much the CLI writes to stderr, apart from errors
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Verbosity {
+    /// Nothing
+    Quiet,
+    /// What was saved or updated
+    Normal,
+    /// Also what was generated or checked
+    Verbose,
+}
+
+impl Verbosity {
+    /// Report what was done, unless quiet.
+    fn info(self, message: impl Display) {
+        if self >= Verbosity::Normal {
+            eprintln!("{}", message);
+        }
+    }
+
+    /// Report the details of what was done, if verbose.
+    fn detail(self, message: impl Display) {
+        if self >= Verbosity::Verbose {
+            eprintln!("{}", message);
+        }
+    }
+}
 
 /// Convenience struct to hold command line options
 struct RSBadgesOptions {
-    /// The requested style of the badge
-    style: Style,
+    /// The requested badge, or grid of badges
+    image: Image,
     /// Should this be opened in browser once generated?
     open_in_browser: bool,
     /// Save the badge to this path
     save_to_path: String,
+    /// Save a light and a dark copy of the badge instead of one
+    dark_pair: bool,
+    /// Print markup that shows the badge, with the image at this URL, or the data URI
+    /// of the badge if there is none
+    snippet: Option<(SnippetFormat, Option<String>)>,
+    /// What to write to stdout
+    output: Output,
+    /// How much to write to stderr
+    verbosity: Verbosity,
+    /// Check that the generated SVG is well-formed before using it
+    #[cfg(feature = "validate")]
+    validate: bool,
+}
+
+/// Create and save an SVG through a CLI tool. Only the badge, or its snippet, goes to
+/// stdout; everything else goes to stderr.
+fn main() -> ExitCode {
+    let result = parse_project_dir_from_args().and_then(|run| match run {
+        Run::Generate(options) => generate(&options).map(|()| ExitCode::SUCCESS),
+        Run::UpdateReadme(update) => update_readme(&update).map(|()| ExitCode::SUCCESS),
+        Run::Help(usage) => write_stdout(&format!("{}\n", usage)).map(|()| ExitCode::SUCCESS),
+        Run::Usage(usage) => {
+            eprintln!("{}", usage);
+            let error = BadgeError::BadCommandLineArgs(String::new());
+            Ok(ExitCode::from(exit_code(&error)))
+        }
+    });
+    match result {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            ExitCode::from(exit_code(&error))
+        }
+    }
 }
 
-/// Create and save an SVG through a CLI tool
-fn main() -> Result<(), BadgeError> {
-    println!("\n");
-    let options = parse_project_dir_from_args()?;
-    let svg = options.style.generate_svg()?;
+/// The exit code of the CLI for each error, so scripts can tell them apart. A failed
+/// --check exits with 1, and bad arguments with 2, as is usual for command line tools.
+fn exit_code(error: &BadgeError) -> u8 {
+    match error {
+        BadgeError::OutOfDate(_) => 1,
+        BadgeError::BadCommandLineArgs(_) => 2,
+        BadgeError::InvalidStyle(_) => 3,
+        BadgeError::ColorNotValid(_) => 4,
+        BadgeError::InvalidScale(_) => 5,
+        BadgeError::InsufficientContrast(_) => 6,
+        BadgeError::CannotReadFile(_) => 7,
+        BadgeError::CannotSaveToFile(_) => 8,
+        BadgeError::CannotEmbedLogo(_) => 9,
+        BadgeError::CannotLoadFont => 10,
+        BadgeError::InvalidSvg(_) => 11,
+        BadgeError::InvalidReport(_) => 12,
+        BadgeError::InvalidManifest(_) => 13,
+        BadgeError::InvalidRepository(_) => 14,
+        BadgeError::InvalidReadme(_) => 15,
+        BadgeError::InvalidIdSuffix(_) => 16,
+    }
+}
 
-    println!("Generated SVG:\n------\n{}\n------\n", svg);
+/// Write the result of the CLI to stdout. A reader that stops early, like `head`, is
+/// not an error.
+fn write_stdout(text: &str) -> Result<(), BadgeError> {
+    let mut stdout = io::stdout().lock();
+    match stdout
+        .write_all(text.as_bytes())
+        .and_then(|_| stdout.flush())
+    {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            Err(BadgeError::CannotSaveToFile(format!("stdout: {}", e)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Generate the requested badge or grid, then save, print and open it as asked.
+fn generate(options: &RSBadgesOptions) -> Result<(), BadgeError> {
+    let verbosity = options.verbosity;
+    let (svg, dark_svg) = match &options.image {
+        Image::Badge(style) if options.dark_pair => {
+            let (light, dark) = style.generate_svg_pair()?;
+            (light, Some(dark))
+        }
+        Image::Badge(style) => (style.generate_svg()?, None),
+        Image::Grid(grid) => (grid.generate_svg()?, None),
+    };
+    verbosity.detail(format!("Generated an SVG of {} bytes", svg.len()));
+    #[cfg(feature = "validate")]
+    if options.validate {
+        rsbadges::validate_svg(&svg)?;
+        if let Some(dark_svg) = &dark_svg {
+            rsbadges::validate_svg(dark_svg)?;
+        }
+        verbosity.detail("The SVG is well-formed");
+    }
 
     // Save our file
-    let mut saved = false;
-    if !options.save_to_path.is_empty() {
-        println!("Saving a badge copy at {:#?}", options.save_to_path);
+    let saved = !options.save_to_path.is_empty();
+    if saved {
         rsbadges::save_svg(&options.save_to_path, &svg)?;
-        saved = true;
+        verbosity.info(format!("Saved the badge to {}", options.save_to_path));
+    }
+    if let Some(dark_svg) = &dark_svg {
+        let dark_path = dark_variant_path(&options.save_to_path);
+        rsbadges::save_svg(&dark_path, dark_svg)?;
+        verbosity.info(format!("Saved the dark badge to {}", dark_path));
+    }
+
+    let data_uri = match options.output {
+        Output::DataUri(encoding) => Some(rsbadges::svg_data_uri(&svg, encoding)),
+        _ => None,
+    };
+    if let Some((format, url)) = &options.snippet {
+        let url = url.as_deref().or(data_uri.as_deref()).unwrap_or_default();
+        let snippet = match &options.image {
+            Image::Badge(style) => badge_of(style).snippet(*format, url),
+            Image::Grid(grid) => grid.snippet(*format, url),
+        };
+        write_stdout(&format!("{}\n", snippet))?;
+    } else if let Some(data_uri) = &data_uri {
+        write_stdout(&format!("{}\n", data_uri))?;
+    } else if options.output == Output::Svg || !(saved || options.open_in_browser) {
+        write_stdout(&svg)?;
     }
 
     // Open in browser, if we can
     if options.open_in_browser {
-        if saved {
-            webbrowser::open(&options.save_to_path).expect("Could not open browser.");
+        let svg_path = if saved {
+            options.save_to_path.clone()
         } else {
-            use std::path::Path;
-            let ci_path = std::env::temp_dir();
-            let ci_path_full = ci_path.join(Path::new("badge.svg"));
+            let ci_path_full = env::temp_dir().join("badge.svg");
             let svg_path = match ci_path_full.to_str() {
-                Some(path) => path,
+                Some(path) => String::from(path),
                 None => {
                     return Err(BadgeError::CannotSaveToFile(String::from(
                         "Path is not valid! Cannot save tmp file for browser display.",
                     )));
                 }
             };
-            println!(
-                "Saving a temporary badge copy for browser display at {:#?}",
+            rsbadges::save_svg(&svg_path, &svg)?;
+            verbosity.detail(format!(
+                "Saved a temporary badge copy for browser display to {}",
                 svg_path
-            );
-            rsbadges::save_svg(svg_path, &svg)?;
-            webbrowser::open(svg_path).expect("Could not open browser.");
+            ));
+            svg_path
+        };
+        match webbrowser::open(&svg_path) {
+            Ok(()) => verbosity.detail(format!("Opened {} in a browser", svg_path)),
+            Err(e) => verbosity.info(format!("Could not open a browser: {}", e)),
         }
     }
     Ok(())
 }
 
-/// Process and save all provided arguments
-fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
-    let args: Vec<String> = env::args().collect();
-    let program = args[0].clone();
+/// Save the badges of the readme-update command and rewrite the badge block of its
+/// README, or with --check, fail if any of them would change.
+fn update_readme(update: &ReadmeUpdate) -> Result<(), BadgeError> {
+    let verbosity = update.verbosity;
+    let mut stale = Vec::new();
+    let mut snippets = Vec::new();
+    for badge in &update.badges {
+        // The same path always gets the same IDs, so unchanged badges stay unchanged
+        let svg = badge
+            .style
+            .generate_svg_with_id_suffix(&path_id_suffix(&badge.save_to_path))?;
+        if std::fs::read_to_string(&badge.save_to_path).ok().as_ref() != Some(&svg) {
+            if update.check {
+                verbosity.detail(format!("{} is out of date", badge.save_to_path));
+            } else {
+                rsbadges::save_svg(&badge.save_to_path, &svg)?;
+                verbosity.info(format!("Saved the badge to {}", badge.save_to_path));
+            }
+            stale.push(badge.save_to_path.as_str());
+        } else {
+            verbosity.detail(format!("{} is up to date", badge.save_to_path));
+        }
+        snippets.push(badge_of(&badge.style).snippet(update.format, &badge.url));
+    }
 
+    let readme = std::fs::read_to_string(&update.readme)
+        .map_err(|e| BadgeError::CannotReadFile(format!("{}: {}", update.readme, e)))?;
+    // Directives of reStructuredText end at a blank line
+    let separator = match update.format {
+        SnippetFormat::ReStructuredText => "\n\n",
+        _ => "\n",
+    };
+    let updated = rsbadges::update_readme_block(&readme, &snippets.join(separator))?;
+    if updated != readme {
+        if update.check {
+            verbosity.detail(format!(
+                "The badge block of {} is out of date",
+                update.readme
+            ));
+        } else {
+            std::fs::write(&update.readme, &updated)
+                .map_err(|e| BadgeError::CannotSaveToFile(format!("{}: {}", update.readme, e)))?;
+            verbosity.info(format!("Updated the badge block of {}", update.readme));
+        }
+        stale.push(update.readme.as_str());
+    }
+
+    if update.check && !stale.is_empty() {
+        return Err(BadgeError::OutOfDate(format!(
+            "Run readme-update without --check to update {}.",
+            stale.join(", ")
+        )));
+    }
+    if stale.is_empty() {
+        verbosity.info(format!("{} and its badges are up to date.", update.readme));
+    }
+    Ok(())
+}
+
+/// The ID suffix of the badge saved at `path`: a hash of the path with FNV-1a, which
+/// unlike the std hasher gives the same result in every Rust release.
+fn path_id_suffix(path: &str) -> String {
+    let hash = path.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:x}", hash)
+}
+
+/// The badge data held by a Style.
+fn badge_of(style: &Style) -> &Badge {
+    match style {
+        Style::Flat(badge)
+        | Style::FlatSquare(badge)
+        | Style::Plastic(badge)
+        | Style::ForTheBadge(badge)
+        | Style::Social(badge) => badge,
+    }
+}
+
+/// The path of the dark copy of a badge saved at `path`: "badge.svg" becomes
+/// "badge-dark.svg".
+fn dark_variant_path(path: &str) -> String {
+    use std::path::Path;
+    let path = Path::new(path);
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push("-dark");
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    path.with_file_name(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The badge that the other options start from: the default badge, or a percentage
+/// badge from --value, or a badge from a command such as `coverage <report>`.
+fn base_badge(matches: &getopts::Matches) -> Result<Badge, BadgeError> {
+    let scale = match matches.opt_str("scale") {
+        Some(spec) => parse_scale(&spec)?,
+        None => ColorScale::coverage(),
+    };
+    let value = matches.opt_str("value");
+    let command = matches.free.first().map(String::as_str);
+    if matches.opt_present("scale") && value.is_none() && command != Some("coverage") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--scale only applies to --value and to the coverage command.",
+        )));
+    }
+    if matches.opt_present("show-skipped") && command != Some("tests") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--show-skipped only applies to the tests command.",
+        )));
+    }
+    if matches.opt_present("package") && command != Some("crate") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--package only applies to the crate command.",
+        )));
+    }
+    let base = match (matches.free.as_slice(), value) {
+        ([], None) => return Ok(Badge::default()),
+        ([], Some(value)) => match value.parse::<f32>() {
+            Ok(value) if value.is_finite() => {
+                // The percentage means nothing without saying what it measures
+                let label = matches.opt_str("label").ok_or_else(|| {
+                    BadgeError::BadCommandLineArgs(String::from(
+                        "--value needs --label, to say what the percentage is of.",
+                    ))
+                })?;
+                Badge::percentage(&label, value, &scale)?
+            }
+            _ => {
+                return Err(BadgeError::BadCommandLineArgs(format!(
+                    "{} is not a valid --value. It should be a number, such as 87.3",
+                    value
+                )))
+            }
+        },
+        (_, Some(_)) => {
+            return Err(BadgeError::BadCommandLineArgs(String::from(
+                "--value can't be combined with a command.",
+            )))
+        }
+        #[cfg(feature = "reports")]
+        ([command, report], None) if command == "coverage" => {
+            rsbadges::Coverage::from_report(report)?.badge(&scale)?
+        }
+        #[cfg(feature = "reports")]
+        ([command, report], None) if command == "tests" => {
+            rsbadges::TestResults::from_report(report)?.badge(matches.opt_present("show-skipped"))
+        }
+        #[cfg(feature = "reports")]
+        ([command, manifest, field], None) if command == "crate" => {
+            crate_badge(manifest, field, matches.opt_str("package"))?
+        }
+        #[cfg(feature = "reports")]
+        ([command, repository, field], None) if command == "git" => git_badge(repository, field)?,
+        ([command, ..], None) => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is not a command, or is missing its arguments. Commands: {}",
+                command, COMMANDS
+            )))
+        }
+    };
+    if matches.opt_present("msg") || matches.opt_present("msg-color") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--value and commands set the message and its color, so they can't be \
+            combined with --msg or --msg-color.",
+        )));
+    }
+    Ok(base)
+}
+
+/// The badge of the crate command: one field of the package in a manifest, or of
+/// the workspace member picked with --package.
+#[cfg(feature = "reports")]
+fn crate_badge(manifest: &str, field: &str, package: Option<String>) -> Result<Badge, BadgeError> {
+    use rsbadges::{CrateField, CrateMetadata};
+    let field = match field {
+        "version" => CrateField::Version,
+        "license" => CrateField::License,
+        "msrv" => CrateField::RustVersion,
+        "edition" => CrateField::Edition,
+        "dependencies" => CrateField::Dependencies,
+        _ => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is not a crate field. Fields: version, license, msrv, edition, \
+                dependencies",
+                field
+            )))
+        }
+    };
+    let crates = CrateMetadata::from_manifest(manifest)?;
+    let names = || {
+        crates
+            .iter()
+            .map(|metadata| metadata.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let metadata = match package {
+        Some(package) => crates
+            .iter()
+            .find(|metadata| metadata.name == package)
+            .ok_or_else(|| {
+                BadgeError::BadCommandLineArgs(format!(
+                    "{} has no package {}. Packages: {}",
+                    manifest,
+                    package,
+                    names()
+                ))
+            })?,
+        // Like Cargo, default to the package of the manifest itself
+        None if crates.len() == 1 || crates[0].manifest_path == std::path::Path::new(manifest) => {
+            &crates[0]
+        }
+        None => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is a workspace, so pick one of its packages with --package: {}",
+                manifest,
+                names()
+            )))
+        }
+    };
+    metadata.badge(field)
+}
+
+/// The badge of the git command: one field of a local repository.
+#[cfg(feature = "reports")]
+fn git_badge(repository: &str, field: &str) -> Result<Badge, BadgeError> {
+    use rsbadges::{GitField, GitMetadata};
+    let field = match field {
+        "tag" => GitField::Tag,
+        "commits-since-tag" => GitField::CommitsSinceTag,
+        "last-commit" => GitField::LastCommit,
+        "branch" => GitField::Branch,
+        "contributors" => GitField::Contributors,
+        _ => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is not a git field. Fields: tag, commits-since-tag, last-commit, \
+                branch, contributors",
+                field
+            )))
+        }
+    };
+    GitMetadata::from_repository(repository)?.badge(field)
+}
+
+/// Parse the --scale option: the name of a built-in scale, or a list of thresholds.
+fn parse_scale(spec: &str) -> Result<ColorScale, BadgeError> {
+    match spec {
+        "coverage" => return Ok(ColorScale::coverage()),
+        "red-yellow-green" => return Ok(ColorScale::red_yellow_green()),
+        _ => {}
+    }
+    // Commas inside a color, as in rgb(...), don't separate thresholds
+    let mut thresholds = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in spec.char_indices().chain([(spec.len(), ',')]) {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                let threshold = &spec[start..i];
+                let parsed = threshold
+                    .split_once(':')
+                    .and_then(|(value, color)| Some((value.trim().parse::<f32>().ok()?, color)))
+                    .filter(|(value, _)| value.is_finite());
+                match parsed {
+                    Some((value, color)) => thresholds.push((value, String::from(color.trim()))),
+                    None => {
+                        return Err(BadgeError::InvalidScale(format!(
+                            "{} is not a threshold. Thresholds look like 80:yellow.",
+                            threshold
+                        )))
+                    }
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    Ok(ColorScale::Thresholds(thresholds))
+}
+
+/// The segments given with --segment, each matched with the --segment-color,
+/// --segment-link and --segment-title in the same position.
+fn parse_segments(matches: &getopts::Matches) -> Result<Vec<Segment>, BadgeError> {
+    let texts = matches.opt_strs("segment");
+    let mut colors = matches.opt_strs("segment-color").into_iter();
+    let mut links = matches.opt_strs("segment-link").into_iter();
+    let mut titles = matches.opt_strs("segment-title").into_iter();
+    if colors.len().max(links.len()).max(titles.len()) > texts.len() {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "Each --segment-color, --segment-link and --segment-title needs a --segment.",
+        )));
+    }
+    Ok(texts
+        .into_iter()
+        .map(|text| Segment {
+            text,
+            color: colors.next().unwrap_or_default(),
+            link: links.next().unwrap_or_default(),
+            title: titles.next().unwrap_or_default(),
+            ..Segment::default()
+        })
+        .collect())
+}
+
+/// The options of the CLI, shared by single badges and the lines of a grid file.
+fn cli_options() -> Options {
     let mut opts = Options::new();
     // Required Args
     opts.optopt(
@@ -129,8 +814,9 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
     opts.optopt(
         "b",
         "label-color",
-        "The background color of the left side of the badge. Supports all valid CSS formats. See \
-        https://www.w3schools.com/colors/colors_picker.asp for examples.",
+        "The background color of the left side of the badge. Supports all valid CSS formats, \
+        as well as linear-gradient(...). See https://www.w3schools.com/colors/colors_picker.asp \
+        for examples.",
         "<css_color>",
     );
     opts.optopt(
@@ -148,8 +834,9 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
     opts.optopt(
         "y",
         "msg-color",
-        "The background color of the right side of the badge. Supports all valid CSS formats. See \
-        https://www.w3schools.com/colors/colors_picker.asp for examples.",
+        "The background color of the right side of the badge. Supports all valid CSS formats, \
+        as well as linear-gradient(...). See https://www.w3schools.com/colors/colors_picker.asp \
+        for examples.",
         "<css_color>",
     );
     opts.optopt(
@@ -158,6 +845,91 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         "The url to redirect to when the right side of the badge is clicked.",
         "<url>",
     );
+    opts.optopt(
+        "",
+        "value",
+        "A percentage to show as the message, such as 87.3 for a coverage badge. The \
+        message color is picked from --scale. Needs --label, and can't be combined with \
+        --msg or --msg-color.",
+        "<number>",
+    );
+    opts.optopt(
+        "",
+        "scale",
+        "The color scale for --value: coverage (the Shields.io coverage colors), \
+        red-yellow-green (a smooth blend), or thresholds such as 0:red,80:yellow,90:green. \
+        Defaults to coverage.",
+        "<coverage,red-yellow-green,value:color,...>",
+    );
+    opts.optopt(
+        "",
+        "package",
+        "The workspace member to show with the crate command. Defaults to the package \
+        of the manifest.",
+        "<name>",
+    );
+    opts.optopt(
+        "",
+        "columns",
+        "The number of badges in each row of the grid command. Defaults to a single row.",
+        "<number>",
+    );
+    opts.optopt(
+        "",
+        "column-gap",
+        "The space between the columns of the grid command, in px. Defaults to 4.",
+        "<px>",
+    );
+    opts.optopt(
+        "",
+        "row-gap",
+        "The space between the rows of the grid command, in px. Defaults to 4.",
+        "<px>",
+    );
+    opts.optopt(
+        "",
+        "align",
+        "Where badges narrower than their column sit in the grid command. Defaults to \
+        start.",
+        "<start,center,end>",
+    );
+    opts.optopt(
+        "",
+        "vertical-align",
+        "Where badges shorter than their row sit in the grid command. Defaults to center.",
+        "<start,center,end>",
+    );
+    opts.optflag(
+        "",
+        "show-skipped",
+        "Add the number of skipped tests to the message of the tests command.",
+    );
+    opts.optmulti(
+        "",
+        "segment",
+        "The text of a segment to add after the message. Repeat it for more segments.",
+        "<string>",
+    );
+    opts.optmulti(
+        "",
+        "segment-color",
+        "The background color of a segment added with --segment, in the same order. \
+        Segments without one use the default message color.",
+        "<css_color>",
+    );
+    opts.optmulti(
+        "",
+        "segment-link",
+        "The url to redirect to when a segment added with --segment is clicked, in the \
+        same order.",
+        "<url>",
+    );
+    opts.optmulti(
+        "",
+        "segment-title",
+        "The title of a segment added with --segment, in the same order.",
+        "<string>",
+    );
     opts.optopt(
         "l",
         "logo",
@@ -183,6 +955,12 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         "Flag. Display the badge in a browser.",
     );
     opts.optflag("h", "help", "Flag. Print arguments to console.");
+    opts.optflag("q", "quiet", "Flag. Write nothing to stderr but errors.");
+    opts.optflag(
+        "v",
+        "verbose",
+        "Flag. Also write to stderr what was generated and checked.",
+    );
     opts.optflag(
         "g",
         "logo-as-label",
@@ -224,6 +1002,139 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         "The title to associate with the right side of the badge.",
         "<string>",
     );
+    opts.optopt(
+        "",
+        "aria-label",
+        "The accessible name of the badge, read out by screen readers. \
+        Defaults to \"label: message\".",
+        "<string>",
+    );
+    opts.optopt(
+        "",
+        "label-text-color",
+        "The text color of the left side of the badge. Picked automatically from \
+        --label-color if not set.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "msg-text-color",
+        "The text color of the right side of the badge. Picked automatically from \
+        --msg-color if not set.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "label-shadow-color",
+        "The text shadow color of the left side of the badge. Picked automatically \
+        from --label-color if not set.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "msg-shadow-color",
+        "The text shadow color of the right side of the badge. Picked automatically \
+        from --msg-color if not set.",
+        "<css_color>",
+    );
+    opts.optflag(
+        "",
+        "hide-shadows",
+        "Flag. Draw the badge text without shadows.",
+    );
+    opts.optopt(
+        "",
+        "contrast",
+        "How to handle text that contrasts too little with its background: ignore it, \
+        fail with an error (strict), or adjust the colors until they meet WCAG level AA \
+        (auto). Defaults to ignore.",
+        "<ignore,strict,auto>",
+    );
+    opts.optopt(
+        "",
+        "page-color",
+        "The color of the page the badge will be shown on. Translucent badge colors are \
+        blended over it. Defaults to white.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "dark-label-color",
+        "The background color of the left side of the badge when the viewer prefers a \
+        dark color scheme.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "dark-msg-color",
+        "The background color of the right side of the badge when the viewer prefers a \
+        dark color scheme.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "dark-label-text-color",
+        "The text color of the left side of the badge in dark mode. Picked automatically \
+        from --dark-label-color if not set.",
+        "<css_color>",
+    );
+    opts.optopt(
+        "",
+        "dark-msg-text-color",
+        "The text color of the right side of the badge in dark mode. Picked automatically \
+        from --dark-msg-color if not set.",
+        "<css_color>",
+    );
+    opts.optflag(
+        "",
+        "dark-pair",
+        "Flag. Save a light and a dark copy of the badge for use with <picture>, instead \
+        of one badge that follows the viewer's color scheme. The dark copy is saved next \
+        to --save-to-svg-at, with -dark added to its file name.",
+    );
+    opts.optopt(
+        "",
+        "snippet",
+        "Print markup that shows the badge, ready to paste into a README. The alt text \
+        comes from --badge-title, or the label and message, and the link from \
+        --badge-link.",
+        "<markdown,html,rst,asciidoc>",
+    );
+    opts.optopt(
+        "",
+        "snippet-url",
+        "The path or URL of the badge image in the --snippet. Defaults to \
+        --save-to-svg-at, or with --output data-uri, to the data URI of the badge.",
+        "<url>",
+    );
+    opts.optopt(
+        "",
+        "output",
+        "Write the badge to stdout as the SVG, even when it is saved, or as a data URI \
+        to inline in HTML or Markdown, base64 or URL-encoded. With --snippet, write \
+        only the snippet.",
+        "<svg,data-uri,data-uri-urlencoded>",
+    );
+    opts.optflag(
+        "",
+        "check",
+        "Flag. With readme-update, change nothing, and fail if the README or any badge \
+        is out of date.",
+    );
+    #[cfg(feature = "validate")]
+    opts.optflag(
+        "",
+        "validate",
+        "Flag. Check that the generated badge is well-formed SVG before saving it.",
+    );
+    opts
+}
+
+/// Process and save all provided arguments
+fn parse_project_dir_from_args() -> Result<Run, BadgeError> {
+    let args: Vec<String> = env::args().collect();
+    let program = args[0].clone();
+    let opts = cli_options();
 
     let matches = match opts.parse(&args[1..]) {
         Ok(m) => m,
@@ -231,13 +1142,345 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
     };
 
     if matches.opt_present("h") || args.len() == 1 {
-        let brief = format!("Usage: {} [options]", program);
-        println!("{}", opts.usage(&brief));
-        std::process::exit(1);
+        let brief = format!(
+            "Usage: {} [command] [options]\n\nCommands: {}",
+            program, COMMANDS
+        );
+        // Asking for help is a success; running without arguments is not
+        if matches.opt_present("h") {
+            return Ok(Run::Help(opts.usage(&brief)));
+        }
+        return Ok(Run::Usage(opts.usage(&brief)));
+    }
+    let verbosity = match (matches.opt_present("quiet"), matches.opt_present("verbose")) {
+        (true, true) => {
+            return Err(BadgeError::BadCommandLineArgs(String::from(
+                "--quiet can't be combined with --verbose.",
+            )))
+        }
+        (true, false) => Verbosity::Quiet,
+        (false, true) => Verbosity::Verbose,
+        (false, false) => Verbosity::Normal,
+    };
+
+    if let [command, readme, list] = matches.free.as_slice() {
+        if command == "readme-update" {
+            return parse_readme_update(&opts, &matches, readme, list, verbosity)
+                .map(Run::UpdateReadme);
+        }
+    }
+    if matches.opt_present("check") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--check only applies to the readme-update command.",
+        )));
     }
 
-    // Get the default Badge settings
-    let badge_default = Badge::default();
+    let image = match matches.free.as_slice() {
+        [command, list] if command == "grid" => Image::Grid(parse_grid(&opts, &matches, list)?),
+        _ => {
+            if let Some(option) = GRID_OPTIONS.iter().find(|name| matches.opt_present(name)) {
+                return Err(BadgeError::BadCommandLineArgs(format!(
+                    "--{} only applies to the grid command.",
+                    option
+                )));
+            }
+            Image::Badge(Box::new(parse_style(&matches)?))
+        }
+    };
+
+    let open_in_browser = matches.opt_present("o");
+    #[allow(clippy::manual_unwrap_or_default)]
+    let save_to_path = match matches.opt_str("save-to-svg-at") {
+        Some(val) => val,
+        None => String::from(""),
+    };
+    let dark_pair = matches.opt_present("dark-pair");
+    if dark_pair && save_to_path.is_empty() {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--dark-pair needs --save-to-svg-at, to know where to save the dark copy.",
+        )));
+    }
+    if dark_pair && matches!(image, Image::Grid(_)) {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--dark-pair can't be combined with the grid command.",
+        )));
+    }
+    let output = match matches.opt_str("output").as_deref() {
+        None => Output::Auto,
+        Some("svg") => Output::Svg,
+        Some("data-uri") => Output::DataUri(DataUriEncoding::Base64),
+        Some("data-uri-urlencoded") => Output::DataUri(DataUriEncoding::UrlEncoded),
+        Some(output) => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is not an output. Outputs: svg, data-uri, data-uri-urlencoded",
+                output
+            )))
+        }
+    };
+    let snippet = match matches.opt_str("snippet") {
+        None => None,
+        Some(_) if output == Output::Svg => {
+            return Err(BadgeError::BadCommandLineArgs(String::from(
+                "--snippet can't be combined with --output svg.",
+            )))
+        }
+        Some(format) => {
+            let format = parse_snippet_format(&format)?;
+            let url = match matches.opt_str("snippet-url") {
+                Some(url) => Some(url),
+                // The snippet shows the data URI itself
+                None if matches!(output, Output::DataUri(_)) => None,
+                None if !save_to_path.is_empty() => Some(save_to_path.clone()),
+                None => {
+                    return Err(BadgeError::BadCommandLineArgs(String::from(
+                        "--snippet needs --save-to-svg-at, --snippet-url or --output \
+                        data-uri, to know where the badge will be.",
+                    )))
+                }
+            };
+            Some((format, url))
+        }
+    };
+    if snippet.is_none() && matches.opt_present("snippet-url") {
+        return Err(BadgeError::BadCommandLineArgs(String::from(
+            "--snippet-url only applies with --snippet.",
+        )));
+    }
+    #[cfg(feature = "validate")]
+    let validate = matches.opt_present("validate");
+
+    Ok(Run::Generate(RSBadgesOptions {
+        image,
+        open_in_browser,
+        save_to_path,
+        dark_pair,
+        snippet,
+        output,
+        verbosity,
+        #[cfg(feature = "validate")]
+        validate,
+    }))
+}
+
+/// Parse the --snippet option.
+fn parse_snippet_format(format: &str) -> Result<SnippetFormat, BadgeError> {
+    match format {
+        "markdown" => Ok(SnippetFormat::Markdown),
+        "html" => Ok(SnippetFormat::Html),
+        "rst" => Ok(SnippetFormat::ReStructuredText),
+        "asciidoc" => Ok(SnippetFormat::AsciiDoc),
+        _ => Err(BadgeError::BadCommandLineArgs(format!(
+            "{} is not a snippet format. Formats: markdown, html, rst, asciidoc",
+            format
+        ))),
+    }
+}
+
+/// Read the badges of the readme-update command from the badge file at `list`.
+/// Each badge needs --save-to-svg-at, and may set where the README finds it with
+/// --snippet-url.
+fn parse_readme_update(
+    opts: &Options,
+    matches: &getopts::Matches,
+    readme: &str,
+    list: &str,
+    verbosity: Verbosity,
+) -> Result<ReadmeUpdate, BadgeError> {
+    // The badges, and where they are saved, come from the badge file
+    if let Some(option) = RUN_OPTIONS
+        .iter()
+        .chain(&BADGE_OPTIONS)
+        .find(|name| !README_UPDATE_OPTIONS.contains(name) && given(matches, name))
+    {
+        return Err(BadgeError::BadCommandLineArgs(format!(
+            "--{} can't be combined with the readme-update command.",
+            option
+        )));
+    }
+    let badges = read_badge_file(opts, list, &["save-to-svg-at", "snippet-url"])?
+        .iter()
+        .map(|line| {
+            let style = parse_style(&line.matches).map_err(|e| line.error(e))?;
+            let save_to_path = line.matches.opt_str("save-to-svg-at").ok_or_else(|| {
+                line.error("Each badge needs --save-to-svg-at, to know where to save it.")
+            })?;
+            let url = line
+                .matches
+                .opt_str("snippet-url")
+                .unwrap_or_else(|| save_to_path.clone());
+            Ok(ReadmeBadge {
+                style,
+                save_to_path,
+                url,
+            })
+        })
+        .collect::<Result<Vec<_>, BadgeError>>()?;
+    Ok(ReadmeUpdate {
+        readme: String::from(readme),
+        badges,
+        format: match matches.opt_str("snippet") {
+            Some(format) => parse_snippet_format(&format)?,
+            None => SnippetFormat::Markdown,
+        },
+        check: matches.opt_present("check"),
+        verbosity,
+    })
+}
+
+/// One line of a badge file: the options of a badge, and where they were given
+struct BadgeLine {
+    /// "file:line", for error messages
+    location: String,
+    /// The options of the badge
+    matches: getopts::Matches,
+}
+
+impl BadgeLine {
+    /// An error about this line.
+    fn error(&self, problem: impl Display) -> BadgeError {
+        BadgeError::BadCommandLineArgs(format!("{}: {}", self.location, problem))
+    }
+}
+
+/// Whether the option `name` was given. Unlike [getopts::Matches::opt_present], this
+/// doesn't panic for options this build leaves out, such as --validate without the
+/// `validate` feature.
+fn given(matches: &getopts::Matches, name: &str) -> bool {
+    matches.opt_defined(name) && matches.opt_present(name)
+}
+
+/// Read a badge file, which lists one badge per line with the same options as a
+/// single badge. Empty lines and lines starting with `#` are skipped. Of the
+/// [RUN_OPTIONS], lines may only give those in `allowed`.
+fn read_badge_file(
+    opts: &Options,
+    list: &str,
+    allowed: &[&str],
+) -> Result<Vec<BadgeLine>, BadgeError> {
+    let contents = std::fs::read_to_string(list)
+        .map_err(|e| BadgeError::CannotReadFile(format!("{}: {}", list, e)))?;
+    let mut lines = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let location = format!("{}:{}", list, number + 1);
+        let in_line =
+            |problem: String| BadgeError::BadCommandLineArgs(format!("{}: {}", location, problem));
+        let matches = opts
+            .parse(split_args(line).map_err(in_line)?)
+            .map_err(|f| in_line(f.to_string()))?;
+        let badge_line = BadgeLine { location, matches };
+        if let Some(command) = badge_line.matches.free.first() {
+            if command == "grid" || command == "readme-update" {
+                return Err(badge_line.error(format!("{} can't be used in a badge file.", command)));
+            }
+        }
+        if let Some(option) = RUN_OPTIONS
+            .iter()
+            .find(|name| !allowed.contains(name) && given(&badge_line.matches, name))
+        {
+            return Err(badge_line.error(format!("--{} can't be used in a badge file.", option)));
+        }
+        lines.push(badge_line);
+    }
+    if lines.is_empty() {
+        return Err(BadgeError::BadCommandLineArgs(format!(
+            "{} doesn't list any badges.",
+            list
+        )));
+    }
+    Ok(lines)
+}
+
+/// Build a grid from the badges listed in the badge file at `list`.
+fn parse_grid(
+    opts: &Options,
+    matches: &getopts::Matches,
+    list: &str,
+) -> Result<BadgeGrid, BadgeError> {
+    let badges = read_badge_file(opts, list, &[])?
+        .iter()
+        .map(|line| parse_style(&line.matches).map_err(|e| line.error(e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let columns = match matches.opt_str("columns") {
+        None => None,
+        Some(value) => Some(value.parse::<usize>().map_err(|_| {
+            BadgeError::BadCommandLineArgs(format!(
+                "{} is not a valid --columns. It should be a whole number.",
+                value
+            ))
+        })?),
+    };
+    let number = |name: &str| -> Result<Option<f32>, BadgeError> {
+        match matches.opt_str(name) {
+            None => Ok(None),
+            Some(value) => match value.parse::<f32>() {
+                Ok(value) if value.is_finite() && value >= 0.0 => Ok(Some(value)),
+                _ => Err(BadgeError::BadCommandLineArgs(format!(
+                    "{} is not a valid --{}. It should be a positive number.",
+                    value, name
+                ))),
+            },
+        }
+    };
+    let alignment = |name: &str, default: Alignment| match matches.opt_str(name).as_deref() {
+        None => Ok(default),
+        Some("start") => Ok(Alignment::Start),
+        Some("center") => Ok(Alignment::Center),
+        Some("end") => Ok(Alignment::End),
+        Some(other) => Err(BadgeError::BadCommandLineArgs(format!(
+            "{} is not a valid --{}. Valid alignments: start, center, end",
+            other, name
+        ))),
+    };
+    let defaults = BadgeGrid::default();
+    Ok(BadgeGrid {
+        badges,
+        columns: columns.unwrap_or(defaults.columns),
+        column_gap: number("column-gap")?.unwrap_or(defaults.column_gap),
+        row_gap: number("row-gap")?.unwrap_or(defaults.row_gap),
+        horizontal_alignment: alignment("align", defaults.horizontal_alignment)?,
+        vertical_alignment: alignment("vertical-align", defaults.vertical_alignment)?,
+    })
+}
+
+/// Split a line of a grid file into arguments, like a shell would: on whitespace,
+/// except inside single or double quotes, and with backslash escapes outside of
+/// single quotes.
+fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut arg: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => args.extend(arg.take()),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                arg.get_or_insert_with(String::new);
+            }
+            (Some(open), c) if c == open => quote = None,
+            (None | Some('"'), '\\') => match chars.next() {
+                Some(escaped) => arg.get_or_insert_with(String::new).push(escaped),
+                None => return Err(String::from("The line ends with a backslash.")),
+            },
+            (_, c) => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(String::from("A quote isn't closed."));
+    }
+    args.extend(arg);
+    Ok(args)
+}
+
+/// Build the badge and its style from the command line options.
+fn parse_style(matches: &getopts::Matches) -> Result<Style, BadgeError> {
+    // Get the default Badge settings, or the ones generated by a command
+    let badge_default = base_badge(matches)?;
 
     let label_text = matches.opt_str("label").unwrap_or(badge_default.label_text);
     let label_color = matches
@@ -267,7 +1510,50 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
     let msg_title = matches
         .opt_str("msg-title")
         .unwrap_or(badge_default.msg_title);
+    let aria_label = matches
+        .opt_str("aria-label")
+        .unwrap_or(badge_default.aria_label);
+    let label_text_color = matches
+        .opt_str("label-text-color")
+        .unwrap_or(badge_default.label_text_color);
+    let msg_text_color = matches
+        .opt_str("msg-text-color")
+        .unwrap_or(badge_default.msg_text_color);
+    let label_shadow_color = matches
+        .opt_str("label-shadow-color")
+        .unwrap_or(badge_default.label_shadow_color);
+    let msg_shadow_color = matches
+        .opt_str("msg-shadow-color")
+        .unwrap_or(badge_default.msg_shadow_color);
+    let hide_shadows = matches.opt_present("hide-shadows");
+    let contrast_mode = match matches.opt_str("contrast").as_deref() {
+        None | Some("ignore") => ContrastMode::Ignore,
+        Some("strict") => ContrastMode::Strict,
+        Some("auto") => ContrastMode::AutoAdjust,
+        Some(other) => {
+            return Err(BadgeError::BadCommandLineArgs(format!(
+                "{} is not a valid contrast mode. Valid modes: ignore, strict, auto",
+                other
+            )))
+        }
+    };
+    let page_color = matches
+        .opt_str("page-color")
+        .unwrap_or(badge_default.page_color);
+    let dark_label_color = matches
+        .opt_str("dark-label-color")
+        .unwrap_or(badge_default.dark_label_color);
+    let dark_msg_color = matches
+        .opt_str("dark-msg-color")
+        .unwrap_or(badge_default.dark_msg_color);
+    let dark_label_text_color = matches
+        .opt_str("dark-label-text-color")
+        .unwrap_or(badge_default.dark_label_text_color);
+    let dark_msg_text_color = matches
+        .opt_str("dark-msg-text-color")
+        .unwrap_or(badge_default.dark_msg_text_color);
     let use_logo_as_label = matches.opt_present("g");
+    let extra_segments = parse_segments(matches)?;
 
     let badge = Badge {
         label_text,
@@ -275,6 +1561,7 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         badge_link,
         label_link,
         msg_link,
+        extra_segments,
         label_color,
         msg_color,
         logo,
@@ -282,6 +1569,18 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         badge_title,
         label_title,
         msg_title,
+        aria_label,
+        label_text_color,
+        msg_text_color,
+        label_shadow_color,
+        msg_shadow_color,
+        hide_shadows,
+        contrast_mode,
+        page_color,
+        dark_label_color,
+        dark_msg_color,
+        dark_label_text_color,
+        dark_msg_text_color,
         use_logo_as_label,
     };
 
@@ -297,16 +1596,5 @@ fn parse_project_dir_from_args() -> Result<RSBadgesOptions, BadgeError> {
         "social" => Style::Social(badge),
         _ => return Err(BadgeError::InvalidStyle(style_str)),
     };
-
-    let open_in_browser = matches.opt_present("o");
-    let save_to_path = match matches.opt_str("save-to-svg-at") {
-        Some(val) => val,
-        None => String::from(""),
-    };
-
-    Ok(RSBadgesOptions {
-        style,
-        open_in_browser,
-        save_to_path,
-    })
+    Ok(style)
 }
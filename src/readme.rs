@@ -0,0 +1,98 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Keep a block of badges in a README up to date
+
+use crate::BadgeError;
+
+/// The comment that starts the badge block of a README.
+pub const README_START: &str = "<!-- rsbadges:start -->";
+
+/// The comment that ends the badge block of a README.
+pub const README_END: &str = "<!-- rsbadges:end -->";
+
+/// Replace the contents of the badge block of `readme`, between [README_START] and
+/// [README_END], with `block`. Everything outside of the block is kept as is, and
+/// the block gets the line endings of the README, so updating a README that is up to
+/// date returns it unchanged.
+///
+/// # Examples
+///
+/// ```
+/// let readme = "# Project\n<!-- rsbadges:start -->\nold\n<!-- rsbadges:end -->\nText\n";
+/// let updated = rsbadges::update_readme_block(readme, "![build: passing](build.svg)").unwrap();
+/// assert_eq!(
+///     updated,
+///     "# Project\n<!-- rsbadges:start -->\n![build: passing](build.svg)\n<!-- rsbadges:end -->\nText\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// [InvalidReadme](BadgeError::InvalidReadme), if the README doesn't have exactly
+/// one start marker, followed by exactly one end marker.
+///
+pub fn update_readme_block(readme: &str, block: &str) -> Result<String, BadgeError> {
+    let find = |marker: &str| -> Result<usize, BadgeError> {
+        let mut found = readme.match_indices(marker).map(|(i, _)| i);
+        match (found.next(), found.next()) {
+            (Some(i), None) => Ok(i),
+            (None, _) => Err(BadgeError::InvalidReadme(format!("{} is missing.", marker))),
+            (Some(_), Some(_)) => Err(BadgeError::InvalidReadme(format!(
+                "{} appears more than once.",
+                marker
+            ))),
+        }
+    };
+    let start = find(README_START)? + README_START.len();
+    let end = find(README_END)?;
+    if end < start {
+        return Err(BadgeError::InvalidReadme(format!(
+            "{} comes before {}.",
+            README_END, README_START
+        )));
+    }
+
+    let newline = if readme.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut contents = String::from(newline);
+    for line in block.lines() {
+        contents += line;
+        contents += newline;
+    }
+    Ok(format!(
+        "{}{}{}",
+        &readme[..start],
+        contents,
+        &readme[end..]
+    ))
+}
@@ -0,0 +1,60 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Read badge data from the reports of other tools, such as code coverage, from crate
+//! manifests, and from git repositories
+
+mod coverage;
+mod git;
+mod manifest;
+mod test_results;
+
+pub use coverage::{Coverage, CoverageFormat};
+pub use git::{GitField, GitMetadata};
+pub use manifest::{CrateField, CrateMetadata};
+pub use test_results::{TestReportFormat, TestResults};
+
+use crate::BadgeError;
+use std::path::Path;
+
+/// Read the report at `path` into a string.
+fn read_report(path: &Path) -> Result<String, BadgeError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| BadgeError::CannotReadFile(format!("{}: {}", path.display(), e)))
+}
+
+/// Parse an XML report, allowing the DTD declarations that many tools write.
+fn parse_xml<'a>(report: &'a str, kind: &str) -> Result<roxmltree::Document<'a>, BadgeError> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..roxmltree::ParsingOptions::default()
+    };
+    roxmltree::Document::parse_with_options(report, options)
+        .map_err(|e| BadgeError::InvalidReport(format!("The {} report is not XML. {}", kind, e)))
+}
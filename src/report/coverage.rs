@@ -0,0 +1,269 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Line coverage from lcov, Cobertura and cargo-tarpaulin reports
+
+use super::{parse_xml, read_report};
+use crate::{Badge, BadgeError, ColorScale};
+use std::path::Path;
+
+/// The format of a code coverage report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageFormat {
+    /// An lcov tracefile, usually `lcov.info`, as written by `cargo llvm-cov --lcov`
+    /// or `grcov`.
+    Lcov,
+    /// A Cobertura XML report, usually `cobertura.xml`.
+    Cobertura,
+    /// The JSON report of `cargo tarpaulin --out Json`, usually `tarpaulin-report.json`.
+    Tarpaulin,
+}
+
+impl CoverageFormat {
+    /// Guess the format of a report from its contents.
+    pub fn detect(report: &str) -> Option<CoverageFormat> {
+        let report = report.trim_start_matches('\u{feff}').trim_start();
+        if report.starts_with('<') {
+            Some(CoverageFormat::Cobertura)
+        } else if report.starts_with('{') {
+            Some(CoverageFormat::Tarpaulin)
+        } else if report.lines().any(|line| {
+            ["TN:", "SF:", "DA:", "LF:"]
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+        }) {
+            Some(CoverageFormat::Lcov)
+        } else {
+            None
+        }
+    }
+}
+
+/// The line coverage of a project, as read from a coverage report.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::{ColorScale, Coverage, CoverageFormat, Style};
+/// let report = "SF:src/lib.rs\nDA:1,1\nDA:2,0\nLF:2\nLH:1\nend_of_record\n";
+/// let coverage = Coverage::parse(report, CoverageFormat::Lcov).unwrap();
+/// assert_eq!(coverage.percent(), 50.0);
+/// let badge = coverage.badge(&ColorScale::coverage()).unwrap();
+/// let svg = Style::Flat(badge).generate_svg().unwrap();
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coverage {
+    /// The number of lines that ran at least once.
+    pub lines_covered: u64,
+    /// The number of lines that could have run.
+    pub lines_valid: u64,
+}
+
+impl Coverage {
+    /// Read the coverage report at `path`, detecting its format from its contents.
+    ///
+    /// # Errors
+    ///
+    /// - [CannotReadFile](BadgeError::CannotReadFile), if the report can't be read
+    /// - [InvalidReport](BadgeError::InvalidReport), if it isn't a coverage report
+    ///   in one of the [CoverageFormat]s
+    ///
+    pub fn from_report(path: impl AsRef<Path>) -> Result<Coverage, BadgeError> {
+        let report = read_report(path.as_ref())?;
+        let format = CoverageFormat::detect(&report).ok_or_else(|| {
+            BadgeError::InvalidReport(format!(
+                "{} is not an lcov, Cobertura or tarpaulin JSON report.",
+                path.as_ref().display()
+            ))
+        })?;
+        Coverage::parse(&report, format)
+    }
+
+    /// Parse a coverage report in the given format.
+    ///
+    /// # Errors
+    ///
+    /// [InvalidReport](BadgeError::InvalidReport), if the report can't be parsed or
+    /// has no lines to cover.
+    ///
+    pub fn parse(report: &str, format: CoverageFormat) -> Result<Coverage, BadgeError> {
+        let coverage = match format {
+            CoverageFormat::Lcov => parse_lcov(report)?,
+            CoverageFormat::Cobertura => parse_cobertura(report)?,
+            CoverageFormat::Tarpaulin => parse_tarpaulin(report)?,
+        };
+        if coverage.lines_valid == 0 {
+            return Err(BadgeError::InvalidReport(String::from(
+                "The report has no lines to cover.",
+            )));
+        }
+        Ok(coverage)
+    }
+
+    /// The share of lines covered, from 0 to 100.
+    pub fn percent(&self) -> f32 {
+        if self.lines_valid == 0 {
+            return 0.0;
+        }
+        (self.lines_covered as f64 * 100.0 / self.lines_valid as f64) as f32
+    }
+
+    /// A `coverage` badge showing this coverage, colored by `scale`. See
+    /// [Badge::percentage].
+    pub fn badge(&self, scale: &ColorScale) -> Result<Badge, BadgeError> {
+        Badge::percentage("coverage", self.percent(), scale)
+    }
+}
+
+/// Sum up the records of an lcov tracefile. Each record gives its totals in `LF` and
+/// `LH`; records without them are counted from their `DA` lines.
+fn parse_lcov(report: &str) -> Result<Coverage, BadgeError> {
+    let invalid = |line: &str| {
+        BadgeError::InvalidReport(format!("{} is not a valid lcov line.", line.trim()))
+    };
+    let count = |value: &str, line: &str| value.trim().parse::<u64>().map_err(|_| invalid(line));
+
+    let mut coverage = Coverage {
+        lines_covered: 0,
+        lines_valid: 0,
+    };
+    // (LF, LH) of the current record, and its counts from DA lines
+    let mut totals: (Option<u64>, Option<u64>) = (None, None);
+    let mut counted = (0, 0);
+    let mut finish_record = |totals: &mut (Option<u64>, Option<u64>), counted: &mut (u64, u64)| {
+        coverage.lines_valid += totals.0.unwrap_or(counted.0);
+        coverage.lines_covered += totals.1.unwrap_or(counted.1);
+        *totals = (None, None);
+        *counted = (0, 0);
+    };
+    for line in report.lines() {
+        let line = line.trim_end();
+        if line == "end_of_record" {
+            finish_record(&mut totals, &mut counted);
+        } else if let Some(value) = line.strip_prefix("LF:") {
+            totals.0 = Some(count(value, line)?);
+        } else if let Some(value) = line.strip_prefix("LH:") {
+            totals.1 = Some(count(value, line)?);
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            let hits = data.split(',').nth(1).ok_or_else(|| invalid(line))?;
+            counted.0 += 1;
+            if count(hits, line)? > 0 {
+                counted.1 += 1;
+            }
+        }
+    }
+    // A final record may be missing its end_of_record
+    finish_record(&mut totals, &mut counted);
+    Ok(coverage)
+}
+
+/// Read the totals of a Cobertura report from its root element, or count its lines
+/// if the totals are missing.
+fn parse_cobertura(report: &str) -> Result<Coverage, BadgeError> {
+    let doc = parse_xml(report, "Cobertura")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("coverage") {
+        return Err(BadgeError::InvalidReport(String::from(
+            "A Cobertura report should start with a <coverage> element.",
+        )));
+    }
+    let attribute = |name: &str| root.attribute(name).and_then(|value| value.parse().ok());
+    if let (Some(lines_covered), Some(lines_valid)) =
+        (attribute("lines-covered"), attribute("lines-valid"))
+    {
+        return Ok(Coverage {
+            lines_covered,
+            lines_valid,
+        });
+    }
+
+    // Lines are listed once for their class, and again for their method
+    let lines = doc.descendants().filter(|node| {
+        node.has_tag_name("line")
+            && node
+                .parent_element()
+                .and_then(|lines| lines.parent_element())
+                .is_some_and(|class| class.has_tag_name("class"))
+    });
+    let mut coverage = Coverage {
+        lines_covered: 0,
+        lines_valid: 0,
+    };
+    for line in lines {
+        coverage.lines_valid += 1;
+        let hits: u64 = line
+            .attribute("hits")
+            .and_then(|hits| hits.parse().ok())
+            .unwrap_or(0);
+        if hits > 0 {
+            coverage.lines_covered += 1;
+        }
+    }
+    Ok(coverage)
+}
+
+/// Read the totals of a tarpaulin JSON report, or add them up from its files if they
+/// are missing.
+fn parse_tarpaulin(report: &str) -> Result<Coverage, BadgeError> {
+    let json: serde_json::Value = serde_json::from_str(report).map_err(|e| {
+        BadgeError::InvalidReport(format!("The tarpaulin report is not JSON. {}", e))
+    })?;
+    let totals = |value: &serde_json::Value| {
+        Some(Coverage {
+            lines_covered: value.get("covered")?.as_u64()?,
+            lines_valid: value.get("coverable")?.as_u64()?,
+        })
+    };
+    if let Some(coverage) = totals(&json) {
+        return Ok(coverage);
+    }
+    let files = json
+        .get("files")
+        .and_then(|files| files.as_array())
+        .ok_or_else(|| {
+            BadgeError::InvalidReport(String::from(
+                "A tarpaulin report should have a list of files.",
+            ))
+        })?;
+    let mut coverage = Coverage {
+        lines_covered: 0,
+        lines_valid: 0,
+    };
+    for file in files {
+        let file_coverage = totals(file).ok_or_else(|| {
+            BadgeError::InvalidReport(String::from(
+                "Each file of a tarpaulin report should have covered and coverable counts.",
+            ))
+        })?;
+        coverage.lines_covered += file_coverage.lines_covered;
+        coverage.lines_valid += file_coverage.lines_valid;
+    }
+    Ok(coverage)
+}
@@ -0,0 +1,182 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Repository metadata from a local git checkout
+
+use crate::{Badge, BadgeError};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+/// A fact about a git repository to show on a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitField {
+    /// The latest tag reachable from `HEAD`.
+    Tag,
+    /// The number of commits since the latest tag, or of all commits if there is no
+    /// tag.
+    CommitsSinceTag,
+    /// The date of the last commit.
+    LastCommit,
+    /// The branch that is checked out.
+    Branch,
+    /// The number of people who authored a commit.
+    Contributors,
+}
+
+/// The metadata of a local git repository, read by running `git`. Nothing is
+/// fetched, so the metadata is only as recent as the checkout.
+///
+/// # Examples
+///
+/// ```no_run
+/// use rsbadges::{GitField, GitMetadata, Style};
+/// let repository = GitMetadata::from_repository(".").unwrap();
+/// let badge = repository.badge(GitField::LastCommit).unwrap();
+/// let svg = Style::Flat(badge).generate_svg().unwrap();
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitMetadata {
+    /// The latest tag reachable from `HEAD`, if there is one.
+    pub tag: Option<String>,
+    /// The number of commits since [tag](GitMetadata::tag), or of all commits if
+    /// there is no tag.
+    pub commits_since_tag: u64,
+    /// The committer date of `HEAD`, such as "2021-06-30".
+    pub last_commit: String,
+    /// The branch that is checked out, or `None` if `HEAD` is detached.
+    pub branch: Option<String>,
+    /// The number of distinct author emails, after applying any `.mailmap`.
+    pub contributors: usize,
+}
+
+impl GitMetadata {
+    /// Read the metadata of the repository that contains `path`.
+    ///
+    /// # Errors
+    ///
+    /// [InvalidRepository](BadgeError::InvalidRepository), if git can't be run,
+    /// `path` isn't in a git repository, or the repository has no commits.
+    ///
+    pub fn from_repository(path: impl AsRef<Path>) -> Result<GitMetadata, BadgeError> {
+        let path = path.as_ref();
+        git(path, &["rev-parse", "--verify", "--quiet", "HEAD"])?;
+
+        // describe and symbolic-ref fail when there is no tag or HEAD is detached
+        let tag = git(path, &["describe", "--tags", "--abbrev=0"]).ok();
+        // A tag may share its name with a branch, or start with a dash
+        let range = match &tag {
+            Some(tag) => format!("refs/tags/{}..HEAD", tag),
+            None => String::from("HEAD"),
+        };
+        let commits_since_tag = git(path, &["rev-list", "--count", "--end-of-options", &range])?
+            .parse()
+            .map_err(|_| {
+                BadgeError::InvalidRepository(String::from("git rev-list didn't print a count."))
+            })?;
+        let branch = git(path, &["symbolic-ref", "--quiet", "--short", "HEAD"]).ok();
+        let last_commit = git(path, &["log", "-1", "--format=%cd", "--date=short"])?;
+        let authors = git(path, &["log", "--format=%aE", "HEAD"])?;
+        let contributors = authors
+            .lines()
+            .map(str::to_lowercase)
+            .collect::<HashSet<_>>()
+            .len();
+
+        Ok(GitMetadata {
+            tag,
+            commits_since_tag,
+            last_commit,
+            branch,
+            contributors,
+        })
+    }
+
+    /// A badge showing one field of the repository, in the default message color.
+    ///
+    /// # Errors
+    ///
+    /// [InvalidRepository](BadgeError::InvalidRepository), for a
+    /// [Tag](GitField::Tag) badge of a repository without tags, or a
+    /// [Branch](GitField::Branch) badge of a detached `HEAD`.
+    ///
+    pub fn badge(&self, field: GitField) -> Result<Badge, BadgeError> {
+        let (label, msg) = match field {
+            GitField::Tag => (
+                String::from("tag"),
+                self.tag.clone().ok_or_else(|| {
+                    BadgeError::InvalidRepository(String::from("The repository has no tags."))
+                })?,
+            ),
+            GitField::CommitsSinceTag => (
+                match &self.tag {
+                    Some(tag) => format!("commits since {}", tag),
+                    None => String::from("commits"),
+                },
+                self.commits_since_tag.to_string(),
+            ),
+            GitField::LastCommit => (String::from("last commit"), self.last_commit.clone()),
+            GitField::Branch => (
+                String::from("branch"),
+                self.branch.clone().ok_or_else(|| {
+                    BadgeError::InvalidRepository(String::from(
+                        "HEAD is detached, so no branch is checked out.",
+                    ))
+                })?,
+            ),
+            GitField::Contributors => (String::from("contributors"), self.contributors.to_string()),
+        };
+        Ok(Badge {
+            label_text: label,
+            msg_text: msg,
+            ..Badge::default()
+        })
+    }
+}
+
+/// Run git in `repository` and return what it printed, trimmed.
+fn git(repository: &Path, args: &[&str]) -> Result<String, BadgeError> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .output()
+        .map_err(|e| BadgeError::InvalidRepository(format!("Unable to run git. {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BadgeError::InvalidRepository(format!(
+            "git {} failed in {}. {}",
+            args.join(" "),
+            repository.display(),
+            stderr.trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
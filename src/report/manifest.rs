@@ -0,0 +1,503 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Crate metadata from Cargo.toml manifests and Cargo.lock files
+
+use super::read_report;
+use crate::{Badge, BadgeError};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A field of a crate's metadata to show on a badge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrateField {
+    /// The version of the crate, such as "v1.1.5".
+    Version,
+    /// The license expression of the crate, such as "MIT OR Apache-2.0".
+    License,
+    /// The minimum supported Rust version, from `rust-version`.
+    RustVersion,
+    /// The Rust edition of the crate.
+    Edition,
+    /// The number of direct dependencies, along with the total number of locked
+    /// dependencies when a Cargo.lock is found.
+    Dependencies,
+}
+
+/// The metadata of a crate, as read from its Cargo.toml, with values inherited from
+/// its workspace filled in.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::{CrateField, CrateMetadata, Style};
+/// let crates = CrateMetadata::from_manifest("Cargo.toml").unwrap();
+/// assert_eq!(crates[0].name, "rsbadges");
+/// let badge = crates[0].badge(CrateField::Edition).unwrap();
+/// assert_eq!(badge.msg_text, "2021");
+/// let svg = Style::Flat(badge).generate_svg().unwrap();
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateMetadata {
+    /// The name of the package.
+    pub name: String,
+    /// The path of the package's Cargo.toml.
+    pub manifest_path: PathBuf,
+    /// The version of the package.
+    pub version: Option<String>,
+    /// The SPDX license expression of the package.
+    pub license: Option<String>,
+    /// The minimum supported Rust version of the package.
+    pub rust_version: Option<String>,
+    /// The Rust edition of the package, which Cargo defaults to "2015".
+    pub edition: String,
+    /// The number of dependencies listed in the manifest, including target-specific
+    /// ones but not dev or build dependencies.
+    pub dependencies: usize,
+    /// The number of packages the package depends on in the workspace's Cargo.lock,
+    /// directly or not. Cargo.lock doesn't tell dev and build dependencies apart, so
+    /// they are included. This is `None` if there is no Cargo.lock.
+    pub locked_dependencies: Option<usize>,
+}
+
+impl CrateMetadata {
+    /// Read the crates of the manifest at `path`: its package, if it has one,
+    /// followed by the members of its workspace, if it is a workspace root. Nothing
+    /// is downloaded.
+    ///
+    /// # Errors
+    ///
+    /// - [CannotReadFile](BadgeError::CannotReadFile), if a manifest can't be read
+    /// - [InvalidManifest](BadgeError::InvalidManifest), if a manifest can't be
+    ///   parsed, or a workspace member or inherited value can't be found
+    ///
+    pub fn from_manifest(path: impl AsRef<Path>) -> Result<Vec<CrateMetadata>, BadgeError> {
+        let path = path.as_ref();
+        let manifest = read_manifest(path)?;
+        let dir = manifest_dir(path);
+        let workspace = match manifest.get("workspace") {
+            Some(_) => Workspace::new(path, &manifest)?,
+            None => find_workspace(path, &manifest)?,
+        };
+
+        let mut crates = Vec::new();
+        if manifest.contains_key("package") {
+            crates.push(package_metadata(path, &manifest, &workspace)?);
+        }
+        if let Some(members) = manifest.get("workspace").and_then(|ws| ws.get("members")) {
+            for member in workspace_members(&dir, members, manifest.get("workspace"))? {
+                let member_path = member.join("Cargo.toml");
+                let member_manifest = read_manifest(&member_path)?;
+                crates.push(package_metadata(
+                    &member_path,
+                    &member_manifest,
+                    &workspace,
+                )?);
+            }
+        }
+        if crates.is_empty() {
+            return Err(BadgeError::InvalidManifest(format!(
+                "{} has no package and no workspace members.",
+                path.display()
+            )));
+        }
+        Ok(crates)
+    }
+
+    /// A badge showing one field of the crate, in the default message color.
+    ///
+    /// # Errors
+    ///
+    /// [InvalidManifest](BadgeError::InvalidManifest), if the crate doesn't set the
+    /// field.
+    ///
+    pub fn badge(&self, field: CrateField) -> Result<Badge, BadgeError> {
+        let missing =
+            |key: &str| BadgeError::InvalidManifest(format!("{} doesn't set {}.", self.name, key));
+        let (label, msg) = match field {
+            CrateField::Version => (
+                "version",
+                format!(
+                    "v{}",
+                    self.version.as_ref().ok_or_else(|| missing("version"))?
+                ),
+            ),
+            CrateField::License => (
+                "license",
+                self.license.clone().ok_or_else(|| missing("license"))?,
+            ),
+            CrateField::RustVersion => (
+                "msrv",
+                self.rust_version
+                    .clone()
+                    .ok_or_else(|| missing("rust-version"))?,
+            ),
+            CrateField::Edition => ("edition", self.edition.clone()),
+            CrateField::Dependencies => (
+                "dependencies",
+                match self.locked_dependencies {
+                    Some(total) => format!("{} direct, {} total", self.dependencies, total),
+                    None => format!("{} direct", self.dependencies),
+                },
+            ),
+        };
+        Ok(Badge {
+            label_text: String::from(label),
+            msg_text: msg,
+            ..Badge::default()
+        })
+    }
+}
+
+/// The values a package inherits from its workspace, and the Cargo.lock it shares
+/// with it. A package outside of any workspace has only its own Cargo.lock.
+struct Workspace {
+    /// The `[workspace.package]` table, which members inherit values from
+    package: Option<toml::Table>,
+    /// The Cargo.lock next to the workspace root, if there is one
+    lock: Option<toml::Table>,
+}
+
+impl Workspace {
+    /// The workspace whose root manifest, at `root_path`, is `root`.
+    fn new(root_path: &Path, root: &toml::Table) -> Result<Workspace, BadgeError> {
+        let package = root
+            .get("workspace")
+            .and_then(|ws| ws.get("package"))
+            .and_then(|package| package.as_table())
+            .cloned();
+        Ok(Workspace {
+            package,
+            lock: read_lock(&manifest_dir(root_path))?,
+        })
+    }
+}
+
+/// Find the workspace of a package that isn't a workspace root, from its
+/// `package.workspace` key or by searching its parent directories like Cargo does.
+fn find_workspace(path: &Path, manifest: &toml::Table) -> Result<Workspace, BadgeError> {
+    let dir = manifest_dir(path);
+    let explicit = manifest
+        .get("package")
+        .and_then(|package| package.get("workspace"))
+        .and_then(|workspace| workspace.as_str());
+    if let Some(root) = explicit {
+        let root_path = dir.join(root).join("Cargo.toml");
+        let root = read_manifest(&root_path)?;
+        return Workspace::new(&root_path, &root);
+    }
+    let absolute = std::fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone());
+    for ancestor in absolute.ancestors().skip(1) {
+        let root_path = ancestor.join("Cargo.toml");
+        if !root_path.is_file() {
+            continue;
+        }
+        let root = read_manifest(&root_path)?;
+        if root.contains_key("workspace") {
+            return Workspace::new(&root_path, &root);
+        }
+    }
+    Ok(Workspace {
+        package: None,
+        lock: read_lock(&dir)?,
+    })
+}
+
+/// Read the metadata of the package in `manifest`.
+fn package_metadata(
+    path: &Path,
+    manifest: &toml::Table,
+    workspace: &Workspace,
+) -> Result<CrateMetadata, BadgeError> {
+    let package = manifest
+        .get("package")
+        .and_then(|package| package.as_table())
+        .ok_or_else(|| {
+            BadgeError::InvalidManifest(format!("{} has no [package] table.", path.display()))
+        })?;
+    let name = package
+        .get("name")
+        .and_then(|name| name.as_str())
+        .ok_or_else(|| {
+            BadgeError::InvalidManifest(format!("{} has no package name.", path.display()))
+        })?;
+    let field = |key: &str| inherited(package, key, workspace, name);
+    let version = field("version")?;
+
+    let mut dependencies = BTreeSet::new();
+    let mut add_dependencies = |table: Option<&toml::Value>| {
+        if let Some(table) = table.and_then(|table| table.as_table()) {
+            dependencies.extend(table.keys().cloned());
+        }
+    };
+    add_dependencies(manifest.get("dependencies"));
+    if let Some(targets) = manifest.get("target").and_then(|target| target.as_table()) {
+        for target in targets.values() {
+            add_dependencies(target.get("dependencies"));
+        }
+    }
+
+    let locked_dependencies = workspace
+        .lock
+        .as_ref()
+        .and_then(|lock| count_locked_dependencies(lock, name, version.as_deref()));
+    Ok(CrateMetadata {
+        name: String::from(name),
+        manifest_path: path.to_path_buf(),
+        version,
+        license: field("license")?,
+        rust_version: field("rust-version")?,
+        edition: field("edition")?.unwrap_or_else(|| String::from("2015")),
+        dependencies: dependencies.len(),
+        locked_dependencies,
+    })
+}
+
+/// A string field of a package, following `field.workspace = true` to the
+/// `[workspace.package]` table.
+fn inherited(
+    package: &toml::Table,
+    key: &str,
+    workspace: &Workspace,
+    name: &str,
+) -> Result<Option<String>, BadgeError> {
+    let value = match package.get(key) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    if let Some(value) = value.as_str() {
+        return Ok(Some(String::from(value)));
+    }
+    let inherits = value
+        .get("workspace")
+        .and_then(|workspace| workspace.as_bool())
+        .unwrap_or(false);
+    if !inherits {
+        return Err(BadgeError::InvalidManifest(format!(
+            "The {} of {} should be a string.",
+            key, name
+        )));
+    }
+    workspace
+        .package
+        .as_ref()
+        .and_then(|package| package.get(key))
+        .and_then(|value| value.as_str())
+        .map(|value| Some(String::from(value)))
+        .ok_or_else(|| {
+            BadgeError::InvalidManifest(format!(
+                "{} inherits its {} from a workspace that doesn't set it.",
+                name, key
+            ))
+        })
+}
+
+/// The directories of the members of a workspace, in order. Members may use `*` and
+/// `?` wildcards, and directories listed in `exclude` or without a Cargo.toml are
+/// skipped.
+fn workspace_members(
+    root: &Path,
+    members: &toml::Value,
+    workspace: Option<&toml::Value>,
+) -> Result<Vec<PathBuf>, BadgeError> {
+    let patterns = |value: Option<&toml::Value>| -> Result<Vec<String>, BadgeError> {
+        match value {
+            None => Ok(Vec::new()),
+            Some(value) => value
+                .as_array()
+                .and_then(|patterns| {
+                    patterns
+                        .iter()
+                        .map(|pattern| pattern.as_str().map(String::from))
+                        .collect()
+                })
+                .ok_or_else(|| {
+                    BadgeError::InvalidManifest(String::from(
+                        "Workspace members and exclusions should be lists of paths.",
+                    ))
+                }),
+        }
+    };
+    let excluded: HashSet<PathBuf> = patterns(workspace.and_then(|ws| ws.get("exclude")))?
+        .iter()
+        .map(|path| normalize(&root.join(path)))
+        .collect();
+
+    let mut dirs = Vec::new();
+    for pattern in patterns(Some(members))? {
+        let mut matches = vec![root.to_path_buf()];
+        for component in Path::new(&pattern).components() {
+            let component = component.as_os_str().to_string_lossy();
+            if !component.contains(['*', '?']) {
+                matches = matches.iter().map(|dir| dir.join(&*component)).collect();
+                continue;
+            }
+            let mut expanded = Vec::new();
+            for dir in &matches {
+                let Ok(entries) = std::fs::read_dir(dir) else {
+                    continue;
+                };
+                let mut names: Vec<String> = entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .filter(|name| wildcard_match(&component, name))
+                    .collect();
+                names.sort();
+                expanded.extend(names.iter().map(|name| dir.join(name)));
+            }
+            matches = expanded;
+        }
+        for dir in matches {
+            let dir = normalize(&dir);
+            if dir == normalize(root) || excluded.contains(&dir) || dirs.contains(&dir) {
+                continue;
+            }
+            if dir.join("Cargo.toml").is_file() {
+                dirs.push(dir);
+            } else if !pattern.contains(['*', '?']) {
+                return Err(BadgeError::InvalidManifest(format!(
+                    "The workspace member {} has no Cargo.toml.",
+                    pattern
+                )));
+            }
+        }
+    }
+    Ok(dirs)
+}
+
+/// Match a file name against a pattern with `*` and `?` wildcards.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // The positions to resume from at the last `*`
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Count the packages that a package depends on in a Cargo.lock, directly or not.
+fn count_locked_dependencies(
+    lock: &toml::Table,
+    name: &str,
+    version: Option<&str>,
+) -> Option<usize> {
+    let packages = lock.get("package")?.as_array()?;
+    let id = |package: &toml::Value| -> Option<(String, String)> {
+        Some((
+            String::from(package.get("name")?.as_str()?),
+            String::from(package.get("version")?.as_str()?),
+        ))
+    };
+    // Dependencies are listed as "name", or as "name version" when the lock file
+    // has several versions of the package
+    let find = |dependency: &str| {
+        let mut parts = dependency.split(' ');
+        let dep_name = parts.next().unwrap_or_default();
+        let dep_version = parts.next();
+        packages.iter().find(|package| {
+            id(package).is_some_and(|(name, version)| {
+                name == dep_name && dep_version.is_none_or(|dep_version| version == dep_version)
+            })
+        })
+    };
+    let root = match version {
+        Some(version) => find(&format!("{} {}", name, version)),
+        None => find(name),
+    }?;
+
+    let mut seen = HashSet::new();
+    seen.insert(id(root)?);
+    let mut queue = vec![root];
+    while let Some(package) = queue.pop() {
+        let dependencies = package
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|dependency| dependency.as_str());
+        for dependency in dependencies {
+            if let Some(found) = find(dependency) {
+                if let Some(found_id) = id(found) {
+                    if seen.insert(found_id) {
+                        queue.push(found);
+                    }
+                }
+            }
+        }
+    }
+    Some(seen.len() - 1)
+}
+
+/// Read and parse a Cargo.toml.
+fn read_manifest(path: &Path) -> Result<toml::Table, BadgeError> {
+    read_report(path)?.parse().map_err(|e| {
+        BadgeError::InvalidManifest(format!("{} is not valid TOML. {}", path.display(), e))
+    })
+}
+
+/// Read and parse the Cargo.lock in `dir`, if there is one.
+fn read_lock(dir: &Path) -> Result<Option<toml::Table>, BadgeError> {
+    let path = dir.join("Cargo.lock");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    read_manifest(&path).map(Some)
+}
+
+/// The directory of a manifest, which is `.` for a bare `Cargo.toml`.
+fn manifest_dir(path: &Path) -> PathBuf {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Remove the `.` components of a path, so that member paths compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != std::path::Component::CurDir)
+        .collect()
+}
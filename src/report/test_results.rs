@@ -0,0 +1,213 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Test results from JUnit XML and libtest JSON reports
+
+use super::{parse_xml, read_report};
+use crate::badge::format_helper::status_color;
+use crate::{Badge, BadgeError};
+use std::path::Path;
+
+/// The format of a test report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestReportFormat {
+    /// A JUnit XML report, as written by `cargo nextest` or `cargo2junit`.
+    JUnit,
+    /// The JSON lines written by `cargo test -- -Z unstable-options --format json`.
+    Libtest,
+}
+
+impl TestReportFormat {
+    /// Guess the format of a report from its contents.
+    pub fn detect(report: &str) -> Option<TestReportFormat> {
+        let report = report.trim_start_matches('\u{feff}').trim_start();
+        if report.starts_with('<') {
+            Some(TestReportFormat::JUnit)
+        } else if report
+            .lines()
+            .any(|line| line.trim_start().starts_with('{'))
+        {
+            Some(TestReportFormat::Libtest)
+        } else {
+            None
+        }
+    }
+}
+
+/// The results of a test run, as read from a test report.
+///
+/// # Examples
+///
+/// ```
+/// use rsbadges::{Style, TestReportFormat, TestResults};
+/// let report = r#"<testsuite><testcase name="a"/><testcase name="b"><failure/></testcase></testsuite>"#;
+/// let results = TestResults::parse(report, TestReportFormat::JUnit).unwrap();
+/// let badge = results.badge(false);
+/// assert_eq!(badge.msg_text, "1 passed, 1 failed");
+/// let svg = Style::Flat(badge).generate_svg().unwrap();
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestResults {
+    /// The number of tests that passed.
+    pub passed: u64,
+    /// The number of tests that failed, including those that errored.
+    pub failed: u64,
+    /// The number of tests that were skipped or ignored.
+    pub skipped: u64,
+}
+
+impl TestResults {
+    /// Read the test report at `path`, detecting its format from its contents.
+    ///
+    /// # Errors
+    ///
+    /// - [CannotReadFile](BadgeError::CannotReadFile), if the report can't be read
+    /// - [InvalidReport](BadgeError::InvalidReport), if it isn't a test report in one
+    ///   of the [TestReportFormat]s
+    ///
+    pub fn from_report(path: impl AsRef<Path>) -> Result<TestResults, BadgeError> {
+        let report = read_report(path.as_ref())?;
+        let format = TestReportFormat::detect(&report).ok_or_else(|| {
+            BadgeError::InvalidReport(format!(
+                "{} is not a JUnit XML or libtest JSON report.",
+                path.as_ref().display()
+            ))
+        })?;
+        TestResults::parse(&report, format)
+    }
+
+    /// Parse a test report in the given format.
+    ///
+    /// # Errors
+    ///
+    /// [InvalidReport](BadgeError::InvalidReport), if the report can't be parsed or
+    /// has no tests.
+    ///
+    pub fn parse(report: &str, format: TestReportFormat) -> Result<TestResults, BadgeError> {
+        let results = match format {
+            TestReportFormat::JUnit => parse_junit(report)?,
+            TestReportFormat::Libtest => parse_libtest(report)?,
+        };
+        if results.passed + results.failed + results.skipped == 0 {
+            return Err(BadgeError::InvalidReport(String::from(
+                "The report has no tests.",
+            )));
+        }
+        Ok(results)
+    }
+
+    /// A `tests` badge such as "412 passed, 3 failed", which is red if any test
+    /// failed and green otherwise. Failures are only listed when there are some, and
+    /// skipped tests only when `show_skipped` is set and there are some.
+    pub fn badge(&self, show_skipped: bool) -> Badge {
+        let mut counts = vec![format!("{} passed", self.passed)];
+        if self.failed > 0 {
+            counts.push(format!("{} failed", self.failed));
+        }
+        if show_skipped && self.skipped > 0 {
+            counts.push(format!("{} skipped", self.skipped));
+        }
+        Badge {
+            label_text: String::from("tests"),
+            msg_text: counts.join(", "),
+            msg_color: String::from(status_color(self.failed == 0)),
+            ..Badge::default()
+        }
+    }
+}
+
+/// Count the test cases of a JUnit report by their outcome.
+fn parse_junit(report: &str) -> Result<TestResults, BadgeError> {
+    let doc = parse_xml(report, "JUnit")?;
+    let root = doc.root_element();
+    if !root.has_tag_name("testsuites") && !root.has_tag_name("testsuite") {
+        return Err(BadgeError::InvalidReport(String::from(
+            "A JUnit report should start with a <testsuites> or <testsuite> element.",
+        )));
+    }
+    let mut results = TestResults {
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+    };
+    for case in doc
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+    {
+        let has_child = |name: &str| case.children().any(|child| child.has_tag_name(name));
+        if has_child("failure") || has_child("error") {
+            results.failed += 1;
+        } else if has_child("skipped") {
+            results.skipped += 1;
+        } else {
+            results.passed += 1;
+        }
+    }
+    Ok(results)
+}
+
+/// Add up the suite totals of libtest JSON output, with one suite per test binary.
+/// Output without any finished suite, such as that of a run that was cut short, is
+/// counted from its test events instead.
+fn parse_libtest(report: &str) -> Result<TestResults, BadgeError> {
+    let mut suites = TestResults {
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+    };
+    let mut tests = suites;
+    let mut finished_suite = false;
+    // Cargo interleaves its own output with the JSON lines
+    let events = report
+        .lines()
+        .filter(|line| line.trim_start().starts_with('{'))
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok());
+    for event in events {
+        let field = |name: &str| event.get(name).and_then(|value| value.as_str());
+        let count = |name: &str| event.get(name).and_then(|value| value.as_u64());
+        match (field("type"), field("event")) {
+            (Some("suite"), Some("ok" | "failed")) => {
+                finished_suite = true;
+                suites.passed += count("passed").unwrap_or(0);
+                suites.failed += count("failed").unwrap_or(0);
+                suites.skipped += count("ignored").unwrap_or(0);
+            }
+            (Some("test"), Some("ok")) => tests.passed += 1,
+            (Some("test"), Some("failed")) => tests.failed += 1,
+            (Some("test"), Some("ignored")) => tests.skipped += 1,
+            _ => {}
+        }
+    }
+    if finished_suite {
+        Ok(suites)
+    } else {
+        Ok(tests)
+    }
+}
@@ -0,0 +1,188 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+mod support;
+
+use rsbadges::{Badge, Segment, Style};
+use support::all_styles;
+
+/// Generate the badge in every style, and check each one with `check`, given the
+/// parsed SVG document.
+fn check_all_styles(badge: &Badge, check: impl Fn(&Style, &roxmltree::Document)) {
+    for style in all_styles(badge) {
+        let svg = style.generate_svg().unwrap();
+        let doc = match roxmltree::Document::parse(&svg) {
+            Ok(doc) => doc,
+            Err(e) => panic!("{:?} is not valid XML: {}\n{}", style, e, svg),
+        };
+        check(&style, &doc);
+    }
+}
+
+/// The titles placed directly under the element `node`.
+fn titles<'a>(node: roxmltree::Node<'a, 'a>) -> Vec<&'a str> {
+    node.children()
+        .filter(|n| n.has_tag_name("title"))
+        .map(|n| n.text().unwrap_or(""))
+        .collect()
+}
+
+#[test]
+fn accessible_name_from_content() {
+    let badge = Badge {
+        label_text: String::from("build"),
+        msg_text: String::from("passing"),
+        ..Badge::default()
+    };
+    check_all_styles(&badge, |style, doc| {
+        let root = doc.root_element();
+        assert!(root.has_tag_name("svg"), "{:?}", style);
+        assert_eq!(root.attribute("role"), Some("img"), "{:?}", style);
+        assert_eq!(
+            root.attribute("aria-label"),
+            Some("build: passing"),
+            "{:?}",
+            style
+        );
+        assert_eq!(titles(root), vec!["build: passing"], "{:?}", style);
+    });
+}
+
+#[test]
+fn accessible_name_without_label() {
+    let badge = Badge {
+        label_text: String::from(""),
+        msg_text: String::from("passing"),
+        ..Badge::default()
+    };
+    check_all_styles(&badge, |style, doc| {
+        let root = doc.root_element();
+        assert_eq!(root.attribute("aria-label"), Some("passing"), "{:?}", style);
+    });
+}
+
+#[test]
+fn accessible_name_override() {
+    let badge = Badge {
+        label_text: String::from("cov"),
+        msg_text: String::from("87%"),
+        aria_label: String::from("Code coverage: 87 percent"),
+        badge_title: String::from("Coverage of the main branch"),
+        ..Badge::default()
+    };
+    check_all_styles(&badge, |style, doc| {
+        let root = doc.root_element();
+        assert_eq!(
+            root.attribute("aria-label"),
+            Some("Code coverage: 87 percent"),
+            "{:?}",
+            style
+        );
+        assert_eq!(
+            titles(root),
+            vec!["Coverage of the main branch"],
+            "{:?}",
+            style
+        );
+    });
+}
+
+#[test]
+fn accessible_name_is_escaped() {
+    let badge = Badge {
+        label_text: String::from("R&D \"docs\""),
+        msg_text: String::from("<ok>"),
+        ..Badge::default()
+    };
+    check_all_styles(&badge, |style, doc| {
+        let root = doc.root_element();
+        assert_eq!(
+            root.attribute("aria-label"),
+            Some("R&D \"docs\": <ok>"),
+            "{:?}",
+            style
+        );
+    });
+}
+
+#[test]
+fn side_titles_are_svg_titles() {
+    let badge = Badge {
+        label_title: String::from("Left side"),
+        msg_title: String::from("Right side"),
+        ..Badge::default()
+    };
+    check_all_styles(&badge, |style, doc| {
+        let side_titles: Vec<&str> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("rect"))
+            .flat_map(titles)
+            .collect();
+        assert_eq!(side_titles, vec!["Left side", "Right side"], "{:?}", style);
+        assert!(
+            doc.descendants().all(|n| !n.has_tag_name("badge_title")),
+            "{:?}",
+            style
+        );
+    });
+}
+
+#[test]
+fn accessible_name_includes_every_segment() {
+    let mut badge = Badge::from_segments(vec![
+        Segment::new("build"),
+        Segment::new("linux"),
+        Segment::new("passing"),
+        Segment {
+            title: String::from("Duration"),
+            ..Segment::new("2m31s")
+        },
+    ]);
+    badge.extra_segments[0].link = String::from("https://example.com/run");
+    check_all_styles(&badge, |style, doc| {
+        let root = doc.root_element();
+        assert_eq!(
+            root.attribute("aria-label"),
+            Some("build: linux, passing, 2m31s"),
+            "{:?}",
+            style
+        );
+        let segment_titles: Vec<&str> = root
+            .descendants()
+            .filter(|n| n.has_tag_name("rect"))
+            .flat_map(titles)
+            .collect();
+        assert_eq!(segment_titles, vec!["Duration"], "{:?}", style);
+        let links: Vec<&str> = root
+            .descendants()
+            .filter(|n| n.has_tag_name("a"))
+            .filter_map(|n| n.attribute(("http://www.w3.org/1999/xlink", "href")))
+            .collect();
+        assert_eq!(links, vec!["https://example.com/run"], "{:?}", style);
+    });
+}
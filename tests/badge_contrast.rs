@@ -0,0 +1,190 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+mod support;
+
+use rsbadges::{Badge, BadgeError, ContrastMode, Segment, SideContrast, Style};
+use support::all_styles;
+
+/// Grey label with the automatic white text, at about 2.8:1.
+fn low_contrast_badge(contrast_mode: ContrastMode) -> Badge {
+    Badge {
+        label_color: String::from("#999"),
+        contrast_mode,
+        ..Badge::default()
+    }
+}
+
+#[test]
+fn contrast_report_ratios() {
+    let badge = Badge {
+        label_color: String::from("black"),
+        label_text_color: String::from("white"),
+        msg_color: String::from("#777"),
+        msg_text_color: String::from("#777"),
+        ..Badge::default()
+    };
+    let report = Style::Flat(badge).contrast_report().unwrap();
+    assert!(!report.passes_aa());
+    let label = report.label.unwrap();
+    let message = report.message.unwrap();
+    assert!((label.ratio - 21.0).abs() < 0.01, "{:?}", label);
+    assert!(label.passes_aaa());
+    assert!((message.ratio - 1.0).abs() < 0.01, "{:?}", message);
+    assert!(!message.passes_aa());
+}
+
+#[test]
+fn contrast_report_skips_sides_without_text() {
+    let badge = Badge {
+        label_text: String::from(""),
+        ..Badge::default()
+    };
+    for style in all_styles(&badge) {
+        let report = style.contrast_report().unwrap();
+        assert_eq!(report.label, None, "{:?}", style);
+        assert!(report.message.is_some(), "{:?}", style);
+    }
+}
+
+#[test]
+fn contrast_automatic_text_colors_pass() {
+    // Mid-tone colors, like the default #007ec6 message (4.4:1), can fall just short
+    for color in ["#555", "navy", "yellow", "white", "black"] {
+        let badge = Badge {
+            label_color: String::from(color),
+            msg_color: String::from(color),
+            contrast_mode: ContrastMode::Strict,
+            ..Badge::default()
+        };
+        for style in all_styles(&badge) {
+            let report = style.contrast_report().unwrap();
+            assert!(report.passes_aa(), "{} {:?}: {:?}", color, style, report);
+        }
+    }
+}
+
+#[test]
+fn contrast_strict_fails_below_aa() {
+    let styles = vec![
+        Style::Flat(low_contrast_badge(ContrastMode::Strict)),
+        Style::Plastic(low_contrast_badge(ContrastMode::Strict)),
+        Style::ForTheBadge(low_contrast_badge(ContrastMode::Strict)),
+    ];
+    for style in styles {
+        match style.generate_svg() {
+            Err(BadgeError::InsufficientContrast(_)) => {}
+            other => panic!("{:?}: {:?}", style, other),
+        }
+        // The report is still available to explain the failure
+        assert!(!style.contrast_report().unwrap().passes_aa());
+    }
+    for style in all_styles(&low_contrast_badge(ContrastMode::Ignore)) {
+        assert!(style.generate_svg().is_ok(), "{:?}", style);
+    }
+}
+
+#[test]
+fn contrast_auto_adjusts_background() {
+    let style = Style::Flat(low_contrast_badge(ContrastMode::AutoAdjust));
+    let label = style.contrast_report().unwrap().label.unwrap();
+    assert!(label.passes_aa(), "{:?}", label);
+    // Only just enough to pass
+    assert!(label.ratio < SideContrast::AA_RATIO + 0.05, "{:?}", label);
+    assert_ne!(label.background, "#999");
+
+    let svg = style.generate_svg().unwrap();
+    assert!(svg.contains(&format!("fill=\"{}\"", label.background)));
+}
+
+#[test]
+fn contrast_auto_adjusts_text_on_fixed_backgrounds() {
+    let badge = Badge {
+        msg_text_color: String::from("#ccc"),
+        contrast_mode: ContrastMode::AutoAdjust,
+        ..Badge::default()
+    };
+    let message = Style::Social(badge)
+        .contrast_report()
+        .unwrap()
+        .message
+        .unwrap();
+    assert_eq!(message.background, "#fafafa");
+    assert_ne!(message.text, "#ccc");
+    assert!(message.passes_aa(), "{:?}", message);
+}
+
+#[test]
+fn contrast_gradient_uses_weakest_point() {
+    let badge = Badge {
+        msg_color: String::from("linear-gradient(to right, navy, yellow)"),
+        msg_text_color: String::from("white"),
+        ..Badge::default()
+    };
+    let report = Style::Flat(badge.clone()).contrast_report().unwrap();
+    let message = report.message.unwrap();
+    assert_eq!(message.background, "#ff0");
+    assert!(!message.passes_aa());
+
+    // Each color of the gradient is adjusted on its own
+    let adjusted = Style::Flat(Badge {
+        contrast_mode: ContrastMode::AutoAdjust,
+        ..badge
+    });
+    assert!(adjusted.contrast_report().unwrap().passes_aa());
+    let svg = adjusted.generate_svg().unwrap();
+    assert!(svg.contains("stop-color=\"#000080\""));
+    assert!(!svg.contains("stop-color=\"#ff0\""));
+}
+
+#[test]
+fn contrast_report_covers_extra_segments() {
+    let mut badge = Badge::from_segments(vec![
+        Segment::new("build"),
+        Segment {
+            color: String::from("#333"),
+            ..Segment::new("linux")
+        },
+        Segment {
+            color: String::from("#999"),
+            ..Segment::new("passing")
+        },
+        Segment::new(""),
+    ]);
+    let report = Style::Flat(badge.clone()).contrast_report().unwrap();
+    assert_eq!(report.extra_segments.len(), 2);
+    assert!(!report.extra_segments[0].as_ref().unwrap().passes_aa());
+    assert_eq!(report.extra_segments[1], None);
+    assert!(!report.passes_aa());
+
+    badge.contrast_mode = ContrastMode::Strict;
+    match Style::Flat(badge).generate_svg() {
+        Err(BadgeError::InsufficientContrast(msg)) => assert!(msg.contains("segment 3"), "{}", msg),
+        other => panic!("Expected InsufficientContrast, got {:?}", other),
+    }
+}
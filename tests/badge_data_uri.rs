@@ -0,0 +1,108 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Badges written as data URIs
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use rsbadges::{svg_data_uri, Badge, BadgeGrid, DataUriEncoding, Style};
+
+/// Undo the percent-encoding of a URL-encoded data URI.
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(&tail[..2]).unwrap();
+            bytes.push(u8::from_str_radix(hex, 16).unwrap());
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).unwrap()
+}
+
+fn badge() -> Style {
+    Style::Flat(Badge {
+        label_text: String::from("build #12"),
+        msg_text: String::from("100% <ok> – ünïcode"),
+        ..Badge::default()
+    })
+}
+
+#[test]
+fn base64_data_uri() {
+    let svg = badge().generate_svg_with_id_suffix("uri").unwrap();
+    let uri = svg_data_uri(&svg, DataUriEncoding::Base64);
+    let data = uri.strip_prefix("data:image/svg+xml;base64,").unwrap();
+    assert_eq!(STANDARD.decode(data).unwrap(), svg.as_bytes());
+
+    let uri = badge().generate_data_uri(DataUriEncoding::Base64).unwrap();
+    let data = uri.strip_prefix("data:image/svg+xml;base64,").unwrap();
+    let decoded = String::from_utf8(STANDARD.decode(data).unwrap()).unwrap();
+    assert!(decoded.starts_with("<svg") && decoded.ends_with("</svg>"));
+}
+
+#[test]
+fn url_encoded_data_uri() {
+    let svg = badge().generate_svg_with_id_suffix("uri").unwrap();
+    let uri = svg_data_uri(&svg, DataUriEncoding::UrlEncoded);
+    let data = uri.strip_prefix("data:image/svg+xml,").unwrap();
+    // Nothing that ends a URL, an HTML attribute or a Markdown link early is left
+    for byte in data.bytes() {
+        assert!(byte.is_ascii_graphic(), "{:?}", byte as char);
+        assert!(!b"\"'<>#&()[]`{}|\\^".contains(&byte), "{:?}", byte as char);
+    }
+    assert_eq!(percent_decode(data), svg);
+    // Attributes may also be quoted with apostrophes
+    assert_eq!(
+        svg_data_uri("<text>it's</text>", DataUriEncoding::UrlEncoded),
+        "data:image/svg+xml,%3Ctext%3Eit%27s%3C/text%3E"
+    );
+
+    let uri = badge()
+        .generate_data_uri(DataUriEncoding::UrlEncoded)
+        .unwrap();
+    let decoded = percent_decode(uri.strip_prefix("data:image/svg+xml,").unwrap());
+    assert!(decoded.starts_with("<svg") && decoded.ends_with("</svg>"));
+}
+
+#[test]
+fn grid_data_uri() {
+    let grid = BadgeGrid {
+        badges: vec![badge(), badge()],
+        ..BadgeGrid::default()
+    };
+    let uri = grid.generate_data_uri(DataUriEncoding::Base64).unwrap();
+    let data = uri.strip_prefix("data:image/svg+xml;base64,").unwrap();
+    let decoded = String::from_utf8(STANDARD.decode(data).unwrap()).unwrap();
+    assert!(decoded.starts_with("<svg") && decoded.ends_with("</svg>"));
+    assert_eq!(decoded.matches("<svg").count(), 3);
+}
@@ -63,6 +63,51 @@ fn error_color_not_valid() {
     }
 }
 
+#[test]
+fn error_text_color_not_valid() {
+    let badge = Badge {
+        label_text_color: String::from("d5d5d5"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        match style.generate_svg() {
+            Err(BadgeError::ColorNotValid(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+    let badge = Badge {
+        msg_shadow_color: String::from("not a color"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        match style.generate_svg() {
+            Err(BadgeError::ColorNotValid(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn error_gradient_not_valid() {
+    for color in [
+        "linear-gradient(red)",
+        "linear-gradient(to nowhere, red, blue)",
+        "linear-gradient(red, notacolor)",
+        "linear-gradient(red, blue",
+    ] {
+        let badge = Badge {
+            label_color: String::from(color),
+            ..Badge::default()
+        };
+        for style in all_styles(badge) {
+            match style.generate_svg() {
+                Err(BadgeError::ColorNotValid(c)) => assert_eq!(c, color),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
 #[test]
 fn error_cannot_embed_logo() {
     let badge = Badge {
@@ -77,3 +122,15 @@ fn error_cannot_embed_logo() {
         }
     }
 }
+
+#[test]
+fn error_invalid_id_suffix() {
+    for suffix in ["two words", "a\"b", "#x", "é"] {
+        for style in all_styles(Badge::default()) {
+            match style.generate_svg_with_id_suffix(suffix) {
+                Err(BadgeError::InvalidIdSuffix(_)) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+}
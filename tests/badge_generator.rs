@@ -31,7 +31,19 @@ use rsbadges::{Badge, Style};
 use std::fs;
 use std::path::Path;
 
+/// Fail the test if the generated badge is not valid SVG.
+#[cfg(feature = "validate")]
+pub fn assert_valid_svg(filename: &str, svg: &str) {
+    if let Err(e) = rsbadges::validate_svg(svg) {
+        panic!("{} is not valid: {}\n{}", filename, e, svg);
+    }
+}
+
+#[cfg(not(feature = "validate"))]
+pub fn assert_valid_svg(_filename: &str, _svg: &str) {}
+
 pub fn save_svg_to_tmp(filename: &str, svg: String) {
+    assert_valid_svg(filename, &svg);
     let ci_path = std::env::temp_dir();
     let svg_path = ci_path.join(Path::new(filename));
     println!("Saving badge to {:#?}", svg_path);
@@ -233,3 +245,314 @@ fn create_badge_with_separate_label_msg_links() {
         save_svg_to_tmp(&(badge_prefix(style) + "badge_link_dual.svg"), svg);
     }
 }
+
+#[test]
+fn create_badge_right_to_left() {
+    let badge = Badge {
+        label_text: String::from("اختبار"),
+        msg_text: String::from("1.2.3"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        // Only the Arabic label is marked as right-to-left
+        let msg_end = svg.find(">1.2.3<").unwrap();
+        let msg_start = svg[..msg_end].rfind("<text").unwrap();
+        assert!(svg[..msg_start].contains("direction=\"rtl\""));
+        assert!(!svg[msg_start..msg_end].contains("direction=\"rtl\""));
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_rtl.svg"), svg);
+    }
+}
+
+#[test]
+fn create_badge_custom_text_colors() {
+    let badge = Badge {
+        label_text_color: String::from("#ffcc00"),
+        msg_text_color: String::from("black"),
+        label_shadow_color: String::from("rgb(1, 2, 3)"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        assert!(svg.contains("fill=\"#fc0\""), "{:?}", style);
+        assert!(svg.contains("fill=\"#000\""), "{:?}", style);
+        if svg.contains("aria-hidden=\"true\" x=") {
+            assert!(svg.contains("fill=\"#010203\""), "{:?}", style);
+        }
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_text_colors.svg"), svg);
+    }
+}
+
+#[test]
+fn create_badge_translucent_colors() {
+    let badge = Badge {
+        label_color: String::from("rgba(0, 0, 0, 0.1)"),
+        msg_color: String::from("#00000080"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge.clone()) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        // Alpha is kept, and colors are rounded to whole channels
+        if !matches!(style, Style::Social(_)) {
+            assert!(svg.contains("fill=\"rgba(0, 0, 0, 0.1)\""), "{:?}", style);
+            assert!(svg.contains("fill=\"rgba(0, 0, 0, 0.502)\""), "{:?}", style);
+        }
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_translucent.svg"), svg);
+    }
+
+    // A faint label reads as light on a white page, but dark on a black page
+    let on_white = Style::Flat(badge.clone()).contrast_report().unwrap();
+    assert_eq!(on_white.label.unwrap().text, "#333");
+    let on_black = Style::Flat(Badge {
+        page_color: String::from("black"),
+        ..badge
+    })
+    .contrast_report()
+    .unwrap();
+    assert_eq!(on_black.label.unwrap().text, "#fff");
+}
+
+#[test]
+fn create_badge_gradient_colors() {
+    let badge = Badge {
+        label_text: String::from("release"),
+        msg_text: String::from("nightly"),
+        msg_color: String::from("linear-gradient(to right, #8a2be2, rgb(255, 0, 0) 70%, orange)"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge.clone()) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        if !matches!(style, Style::Social(_)) {
+            assert!(
+                svg.contains("x1=\"0\" y1=\"0.5\" x2=\"1\" y2=\"0.5\""),
+                "{:?}",
+                style
+            );
+            assert!(
+                svg.contains("<stop offset=\"0\" stop-color=\"#8a2be2\"/>"),
+                "{:?}",
+                style
+            );
+            assert!(
+                svg.contains("<stop offset=\"0.7\" stop-color=\"#f00\"/>"),
+                "{:?}",
+                style
+            );
+            assert!(
+                svg.contains("<stop offset=\"1\" stop-color=\"#ffa500\"/>"),
+                "{:?}",
+                style
+            );
+            assert!(svg.contains("fill=\"url(#seg1_fill"), "{:?}", style);
+        }
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_gradient.svg"), svg);
+    }
+
+    // Two badges on one page don't share gradient IDs
+    let first = Style::Flat(badge.clone()).generate_svg().unwrap();
+    let second = Style::Flat(badge).generate_svg().unwrap();
+    let gradient_id = |svg: &str| svg.split("id=\"seg1_fill").nth(1).unwrap()[..7].to_string();
+    assert_ne!(gradient_id(&first), gradient_id(&second));
+}
+
+#[test]
+fn create_badge_gradient_accent_colors() {
+    // Mostly light on average, so the text is dark even though it starts out black
+    let badge = Badge {
+        msg_color: String::from("linear-gradient(90deg, black, white 20%, white)"),
+        ..Badge::default()
+    };
+    let report = Style::Flat(badge).contrast_report().unwrap();
+    assert_eq!(report.message.unwrap().text, "#333");
+
+    let badge = Badge {
+        msg_color: String::from("linear-gradient(black, #333, white)"),
+        ..Badge::default()
+    };
+    let svg = Style::Flat(badge).generate_svg().unwrap();
+    // Without a direction, gradients run top to bottom
+    assert!(svg.contains("x1=\"0.5\" y1=\"0\" x2=\"0.5\" y2=\"1\""));
+    assert!(svg.contains("<stop offset=\"0.5\" stop-color=\"#333\"/>"));
+}
+
+#[test]
+fn create_badge_dark_mode() {
+    let badge = Badge {
+        dark_label_color: String::from("#0d1117"),
+        dark_msg_color: String::from("#238636"),
+        dark_msg_text_color: String::from("#f0f6fc"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge.clone()) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        assert!(
+            svg.contains("@media (prefers-color-scheme: dark){"),
+            "{:?}",
+            style
+        );
+        // Text is picked from the dark background unless it is given
+        assert!(svg.contains("{fill:#0d1117}"), "{:?}", style);
+        assert!(svg.contains("{fill:#238636}"), "{:?}", style);
+        assert!(svg.contains("{fill:#f0f6fc}"), "{:?}", style);
+        assert!(svg.contains("{fill:#fff}"), "{:?}", style);
+        assert!(svg.contains("class=\"seg0-fill"), "{:?}", style);
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_dark_mode.svg"), svg);
+    }
+
+    // Without dark colors, there is no dark mode style
+    for style in all_styles(Badge::default()) {
+        let svg = style.generate_svg().unwrap();
+        assert!(!svg.contains("prefers-color-scheme"), "{:?}", style);
+        assert!(!svg.contains("class="), "{:?}", style);
+    }
+}
+
+#[test]
+fn create_badge_dark_pair() {
+    let badge = Badge {
+        dark_msg_color: String::from("#238636"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let (light, dark) = match style.generate_svg_pair() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        for svg in [&light, &dark] {
+            assert!(!svg.contains("prefers-color-scheme"), "{:?}", style);
+        }
+        if matches!(style, Style::Social(_)) {
+            assert!(light.contains("fill=\"#fafafa\""));
+            assert!(!dark.contains("fill=\"#fafafa\""));
+        } else {
+            assert!(light.contains("fill=\"#007ec6\""), "{:?}", style);
+            assert!(!dark.contains("fill=\"#007ec6\""), "{:?}", style);
+        }
+        assert!(dark.contains("fill=\"#238636\""), "{:?}", style);
+        let prefix = badge_prefix(style);
+        save_svg_to_tmp(&(prefix.clone() + "badge_light.svg"), light);
+        save_svg_to_tmp(&(prefix + "badge_dark.svg"), dark);
+    }
+}
+
+#[test]
+fn create_badge_without_shadows() {
+    let badge = Badge {
+        hide_shadows: true,
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let svg = match style.generate_svg() {
+            Ok(f) => f,
+            Err(_) => unreachable!(),
+        };
+        assert_eq!(svg.matches("<text").count(), 2, "{:?}", style);
+        save_svg_to_tmp(&(badge_prefix(style) + "badge_no_shadows.svg"), svg);
+    }
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn validate_accepts_every_style() {
+    let badge = Badge {
+        label_text: String::from("R&D <docs>"),
+        msg_text: String::from(""),
+        label_link: String::from("https://example.com/?a=1&b=2"),
+        label_title: String::from("left"),
+        msg_title: String::from("right"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let svg = style.generate_svg().unwrap();
+        assert_eq!(rsbadges::validate_svg(&svg), Ok(()), "{:?}", style);
+    }
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn validate_rejects_malformed_xml() {
+    let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="10"></svg>"#;
+    assert!(matches!(
+        rsbadges::validate_svg(svg),
+        Err(rsbadges::BadgeError::InvalidSvg(_))
+    ));
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn validate_rejects_known_bad_attributes() {
+    let bad_svgs = [
+        // Color missing its #
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><path d="M0 0" stroke="d5d5d5"/></svg>"#,
+        // Width that is not a number
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><rect width="NaN" height="20"/></svg>"#,
+        // Element that is not part of SVG
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><badge_title>test</badge_title></svg>"#,
+        // Duplicate ids
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><g id="a"/><g id="a"/></svg>"#,
+    ];
+    for svg in bad_svgs {
+        assert!(
+            matches!(
+                rsbadges::validate_svg(svg),
+                Err(rsbadges::BadgeError::InvalidSvg(_))
+            ),
+            "{}",
+            svg
+        );
+    }
+}
+
+#[cfg(feature = "validate")]
+#[test]
+fn validate_rejects_dangling_references() {
+    let bad_svgs = [
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><g clip-path="url(#round)"/></svg>"#,
+        r#"<svg xmlns="http://www.w3.org/2000/svg"><style>a:hover #l{fill:url(#smooth)}</style><linearGradient id="round"/></svg>"#,
+    ];
+    for svg in bad_svgs {
+        assert!(
+            matches!(
+                rsbadges::validate_svg(svg),
+                Err(rsbadges::BadgeError::InvalidSvg(_))
+            ),
+            "{}",
+            svg
+        );
+    }
+}
+
+#[test]
+fn create_badges_with_id_suffix() {
+    let badge = Badge {
+        dark_msg_color: String::from("#0d1117"),
+        ..Badge::default()
+    };
+    for style in all_styles(badge) {
+        let svg = style.generate_svg_with_id_suffix("readme-1_a").unwrap();
+        // Every ID the badge has ends with the suffix
+        for id in svg.split(" id=\"").skip(1) {
+            assert!(id.split('"').next().unwrap().ends_with("readme-1_a"));
+        }
+        // The same suffix always gives the same SVG
+        assert_eq!(
+            svg,
+            style.generate_svg_with_id_suffix("readme-1_a").unwrap()
+        );
+    }
+}
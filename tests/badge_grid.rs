@@ -0,0 +1,188 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Several badges laid out in one SVG with BadgeGrid
+
+#[cfg(feature = "shaping")]
+mod support;
+
+use rsbadges::{Alignment, Badge, BadgeGrid, Style};
+use std::collections::HashSet;
+
+fn badge(label: &str, msg: &str) -> Badge {
+    Badge {
+        label_text: String::from(label),
+        msg_text: String::from(msg),
+        ..Badge::default()
+    }
+}
+
+/// The x and y of each badge placed in a grid SVG, in order.
+fn positions(svg: &str) -> Vec<(f32, f32)> {
+    svg.split("<g transform=\"translate(")
+        .skip(1)
+        .map(|rest| {
+            let (x, y) = rest.split(')').next().unwrap().split_once(' ').unwrap();
+            (x.parse().unwrap(), y.parse().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn grid_in_one_row() {
+    let widths: Vec<f32> = ["a", "bb", "ccc"]
+        .iter()
+        .map(|msg| Style::Flat(badge("x", msg)).layout().unwrap().width)
+        .collect();
+    let grid = BadgeGrid {
+        badges: ["a", "bb", "ccc"]
+            .iter()
+            .map(|msg| Style::Flat(badge("x", msg)))
+            .collect(),
+        column_gap: 5.0,
+        ..BadgeGrid::default()
+    };
+    let svg = grid.generate_svg().unwrap();
+    assert_eq!(
+        positions(&svg),
+        [
+            (0.0, 0.0),
+            (widths[0] + 5.0, 0.0),
+            (widths[0] + widths[1] + 10.0, 0.0)
+        ]
+    );
+    let total = widths.iter().sum::<f32>() + 10.0;
+    assert!(svg.starts_with(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\"  xmlns:xlink=\"http://www.w3.org/1999/xlink\" width=\"{}\" height=\"20\">",
+        total
+    )));
+}
+
+#[test]
+fn grid_rows_and_alignment() {
+    let small = Style::Flat(badge("a", "b")).layout().unwrap();
+    let large = Style::ForTheBadge(badge("wide label", "wide message"))
+        .layout()
+        .unwrap();
+    let grid = BadgeGrid {
+        badges: vec![
+            Style::Flat(badge("a", "b")),
+            Style::ForTheBadge(badge("wide label", "wide message")),
+            Style::ForTheBadge(badge("wide label", "wide message")),
+            Style::Flat(badge("a", "b")),
+        ],
+        columns: 2,
+        column_gap: 0.0,
+        row_gap: 10.0,
+        horizontal_alignment: Alignment::End,
+        vertical_alignment: Alignment::Start,
+    };
+    let svg = grid.generate_svg().unwrap();
+    // Both columns are as wide as the ForTheBadge badge, and both rows as tall
+    let cell = large.width;
+    let row = large.height;
+    assert_eq!(
+        positions(&svg),
+        [
+            (cell - small.width, 0.0),
+            (cell, 0.0),
+            (0.0, row + 10.0),
+            (cell + cell - small.width, row + 10.0)
+        ]
+    );
+}
+
+#[test]
+fn grid_ids_stay_unique() {
+    // Social badges also give IDs to the parts they highlight on hover
+    let grid = BadgeGrid {
+        badges: (0..4)
+            .map(|i| {
+                Style::Social(Badge {
+                    msg_link: format!("https://example.com/{}", i),
+                    ..badge("stars", "42")
+                })
+            })
+            .collect(),
+        ..BadgeGrid::default()
+    };
+    let svg = grid.generate_svg().unwrap();
+    let ids: Vec<&str> = svg
+        .split(" id=\"")
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap())
+        .collect();
+    assert_eq!(ids.len(), 4 * 4);
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), ids.len());
+    for i in 0..4 {
+        assert!(svg.contains(&format!("xlink:href=\"https://example.com/{}\"", i)));
+    }
+    #[cfg(feature = "validate")]
+    rsbadges::validate_svg(&svg).unwrap();
+}
+
+#[test]
+fn grid_with_id_suffix() {
+    let grid = BadgeGrid {
+        badges: vec![
+            Style::Social(badge("stars", "42")),
+            Style::Social(badge("forks", "7")),
+        ],
+        ..BadgeGrid::default()
+    };
+    let svg = grid.generate_svg_with_id_suffix("readme").unwrap();
+    assert_eq!(svg, grid.generate_svg_with_id_suffix("readme").unwrap());
+    assert!(svg.contains(" id=\"smoothreadme_g0\""));
+    assert!(svg.contains(" id=\"smoothreadme_g1\""));
+    assert!(grid.generate_svg_with_id_suffix("no spaces").is_err());
+}
+
+#[test]
+fn empty_grid() {
+    let svg = BadgeGrid::default().generate_svg().unwrap();
+    assert!(svg.contains("width=\"0\" height=\"0\""));
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn visual_grid() {
+    let grid = BadgeGrid {
+        badges: vec![
+            Style::Flat(badge("build", "passing")),
+            Style::Plastic(badge("coverage", "87%")),
+            Style::ForTheBadge(badge("made with", "rust")),
+            Style::Social(badge("stars", "42")),
+        ],
+        columns: 2,
+        horizontal_alignment: Alignment::Center,
+        ..BadgeGrid::default()
+    };
+    let svg = grid.generate_svg().unwrap();
+    support::visual::compare_to_reference("grid", &svg).unwrap();
+}
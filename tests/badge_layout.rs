@@ -0,0 +1,174 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+mod support;
+
+use rsbadges::{Badge, BadgeLayout, Segment, Style};
+use support::all_styles;
+
+/// Read a numeric attribute from the first element that has it.
+fn svg_attr(svg: &str, attr: &str) -> f32 {
+    let key = format!(" {}=\"", attr);
+    let start = svg.find(&key).unwrap() + key.len();
+    let end = start + svg[start..].find('"').unwrap();
+    svg[start..end].parse().unwrap()
+}
+
+fn logo_badge() -> Badge {
+    Badge {
+        label_text: String::from("build"),
+        msg_text: String::from("passing"),
+        logo: String::from("https://simpleicons.org/icons/rust.svg"),
+        ..Badge::default()
+    }
+}
+
+#[test]
+fn layout_matches_svg_size() {
+    for style in all_styles(&logo_badge()) {
+        let layout = style.layout().unwrap();
+        let svg = style.generate_svg().unwrap();
+        assert_eq!(layout.width, svg_attr(&svg, "width"), "{:?}", style);
+        assert_eq!(layout.height, svg_attr(&svg, "height"), "{:?}", style);
+    }
+}
+
+#[test]
+fn layout_sides_fit_within_badge() {
+    for style in all_styles(&logo_badge()) {
+        let BadgeLayout {
+            width,
+            height,
+            label,
+            message,
+            ..
+        } = style.layout().unwrap();
+        assert!(label.x >= 0.0 && label.y >= 0.0, "{:?}", style);
+        assert!(label.x + label.width <= message.x, "{:?}", style);
+        assert!(message.x + message.width <= width, "{:?}", style);
+        assert!(label.y + label.height <= height, "{:?}", style);
+        assert!(message.y + message.height <= height, "{:?}", style);
+    }
+}
+
+#[test]
+fn layout_text_and_logo_stay_on_their_side() {
+    for style in all_styles(&logo_badge()) {
+        let layout = style.layout().unwrap();
+        let logo = layout.logo.unwrap();
+        let label_text_left = layout.label_text.center_x - (0.5 * layout.label_text.width);
+        let msg_text_left = layout.msg_text.center_x - (0.5 * layout.msg_text.width);
+        let msg_text_right = layout.msg_text.center_x + (0.5 * layout.msg_text.width);
+
+        assert!(logo.x >= layout.label.x, "{:?}", style);
+        assert!(logo.x + logo.width <= label_text_left, "{:?}", style);
+        assert!(
+            logo.y >= 0.0 && logo.y + logo.height <= layout.height,
+            "{:?}",
+            style
+        );
+        assert!(msg_text_left >= layout.message.x, "{:?}", style);
+        assert!(
+            msg_text_right <= layout.message.x + layout.message.width,
+            "{:?}",
+            style
+        );
+        assert!(layout.label_text.baseline_y < layout.height, "{:?}", style);
+    }
+}
+
+#[test]
+fn layout_without_logo() {
+    for style in all_styles(&Badge::default()) {
+        assert_eq!(style.layout().unwrap().logo, None, "{:?}", style);
+    }
+}
+
+#[test]
+fn layout_hides_empty_social_message() {
+    let badge = Badge {
+        msg_text: String::from(""),
+        ..Badge::default()
+    };
+    let layout = Style::Social(badge).layout().unwrap();
+    assert_eq!(layout.message.width, 0.0);
+    assert_eq!(layout.width, layout.label.width + 1.0);
+}
+
+fn four_segment_badge() -> Badge {
+    Badge::from_segments(vec![
+        Segment::new("build"),
+        Segment::new("linux"),
+        Segment {
+            color: String::from("#4c1"),
+            ..Segment::new("passing")
+        },
+        Segment::new("2m31s"),
+    ])
+}
+
+#[test]
+fn layout_extra_segments_follow_in_order() {
+    for style in all_styles(&four_segment_badge()) {
+        let layout = style.layout().unwrap();
+        let svg = style.generate_svg().unwrap();
+        assert_eq!(layout.width, svg_attr(&svg, "width"), "{:?}", style);
+        assert_eq!(layout.extra_segments.len(), 2, "{:?}", style);
+
+        let mut right = layout.message.x + layout.message.width;
+        for segment in &layout.extra_segments {
+            let text_left = segment.text.center_x - (0.5 * segment.text.width);
+            let text_right = segment.text.center_x + (0.5 * segment.text.width);
+            assert!(right <= segment.rect.x, "{:?}", style);
+            assert!(segment.rect.width > 0.0, "{:?}", style);
+            assert!(text_left >= segment.rect.x, "{:?}", style);
+            assert!(
+                text_right <= segment.rect.x + segment.rect.width,
+                "{:?}",
+                style
+            );
+            right = segment.rect.x + segment.rect.width;
+        }
+        assert!(right <= layout.width, "{:?}", style);
+    }
+}
+
+#[test]
+fn layout_two_segments_match_badge() {
+    let badge = Badge {
+        label_text: String::from("build"),
+        msg_text: String::from("passing"),
+        ..Badge::default()
+    };
+    let from_segments = Badge::from_segments(badge.segments());
+    assert_eq!(from_segments.segments(), badge.segments());
+    for (style, segments_style) in all_styles(&badge).iter().zip(all_styles(&from_segments)) {
+        assert_eq!(style.layout().unwrap(), segments_style.layout().unwrap());
+        assert!(style.layout().unwrap().extra_segments.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+use rsbadges::{Badge, BadgeError, ColorScale, Style};
+
+fn thresholds(points: &[(f32, &str)]) -> ColorScale {
+    ColorScale::Thresholds(
+        points
+            .iter()
+            .map(|(value, color)| (*value, String::from(*color)))
+            .collect(),
+    )
+}
+
+#[test]
+fn percentage_message_text() {
+    let scale = ColorScale::coverage();
+    let text = |value| {
+        Badge::percentage("coverage", value, &scale)
+            .unwrap()
+            .msg_text
+    };
+    assert_eq!(text(87.3), "87.3%");
+    assert_eq!(text(100.0), "100%");
+    assert_eq!(text(0.0), "0%");
+    assert_eq!(text(99.96), "100%");
+}
+
+#[test]
+fn percentage_keeps_label_and_defaults() {
+    let badge = Badge::percentage("coverage", 42.0, &ColorScale::coverage()).unwrap();
+    assert_eq!(badge.label_text, "coverage");
+    assert_eq!(badge.label_color, Badge::default().label_color);
+    assert!(Style::Flat(badge).generate_svg().is_ok());
+}
+
+#[test]
+fn coverage_scale_thresholds() {
+    let scale = ColorScale::coverage();
+    assert_eq!(scale.color_for(12.0).unwrap(), "#e05d44");
+    assert_eq!(scale.color_for(49.9).unwrap(), "#e05d44");
+    assert_eq!(scale.color_for(50.0).unwrap(), "#fe7d37");
+    assert_eq!(scale.color_for(89.9).unwrap(), "#97ca00");
+    assert_eq!(scale.color_for(90.0).unwrap(), "#4c1");
+    assert_eq!(scale.color_for(100.0).unwrap(), "#4c1");
+}
+
+#[test]
+fn threshold_scale_order_does_not_matter() {
+    let scale = thresholds(&[(90.0, "green"), (0.0, "red"), (75.0, "yellow")]);
+    assert_eq!(scale.color_for(-5.0).unwrap(), "#f00");
+    assert_eq!(scale.color_for(80.0).unwrap(), "#ff0");
+    assert_eq!(scale.color_for(95.0).unwrap(), "#008000");
+
+    // Values below every threshold get the lowest color
+    let scale = thresholds(&[(50.0, "orange"), (80.0, "green")]);
+    assert_eq!(scale.color_for(10.0).unwrap(), "#ffa500");
+}
+
+#[test]
+fn interpolated_scale_blends_colors() {
+    let scale = ColorScale::Interpolated(vec![
+        (0.0, String::from("#000")),
+        (100.0, String::from("#fff")),
+    ]);
+    assert_eq!(scale.color_for(0.0).unwrap(), "#000");
+    assert_eq!(scale.color_for(50.0).unwrap(), "#808080");
+    assert_eq!(scale.color_for(100.0).unwrap(), "#fff");
+    assert_eq!(scale.color_for(-10.0).unwrap(), "#000");
+    assert_eq!(scale.color_for(250.0).unwrap(), "#fff");
+}
+
+#[test]
+fn red_yellow_green_scale() {
+    let scale = ColorScale::red_yellow_green();
+    assert_eq!(scale.color_for(0.0).unwrap(), "#e05d44");
+    assert_eq!(scale.color_for(50.0).unwrap(), "#dfb317");
+    assert_eq!(scale.color_for(100.0).unwrap(), "#4c1");
+    let badge = Badge::percentage("coverage", 75.0, &scale).unwrap();
+    assert_ne!(badge.msg_color, scale.color_for(50.0).unwrap());
+    assert_ne!(badge.msg_color, scale.color_for(100.0).unwrap());
+}
+
+#[test]
+fn empty_scale_is_invalid() {
+    for scale in [
+        ColorScale::Thresholds(vec![]),
+        ColorScale::Interpolated(vec![]),
+    ] {
+        assert!(matches!(
+            Badge::percentage("coverage", 50.0, &scale),
+            Err(BadgeError::InvalidScale(_))
+        ));
+    }
+}
+
+#[test]
+fn interpolated_scale_checks_colors() {
+    let scale = ColorScale::Interpolated(vec![
+        (0.0, String::from("red")),
+        (100.0, String::from("not-a-color")),
+    ]);
+    assert_eq!(
+        scale.color_for(50.0),
+        Err(BadgeError::ColorNotValid(String::from("not-a-color")))
+    );
+}
+
+#[test]
+fn threshold_scale_checks_colors() {
+    let scale = thresholds(&[(0.0, "red"), (50.0, "not-a-color")]);
+    assert_eq!(scale.color_for(10.0).unwrap(), "#f00");
+    assert_eq!(
+        scale.color_for(60.0),
+        Err(BadgeError::ColorNotValid(String::from("not-a-color")))
+    );
+}
+
+#[test]
+fn scales_reject_values_that_are_not_numbers() {
+    for scale in [ColorScale::coverage(), ColorScale::red_yellow_green()] {
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                scale.color_for(value),
+                Err(BadgeError::InvalidScale(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn scales_reject_points_that_are_not_numbers() {
+    for point in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        let points = vec![(0.0, String::from("red")), (point, String::from("green"))];
+        for scale in [
+            ColorScale::Thresholds(points.clone()),
+            ColorScale::Interpolated(points),
+        ] {
+            assert!(matches!(
+                scale.color_for(50.0),
+                Err(BadgeError::InvalidScale(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,254 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Compare generated badges against the golden SVGs in `tests/snapshots`.
+//!
+//! To accept new output after an intended change, run the tests with
+//! `RSBADGES_BLESS=1` and review the updated files before committing them.
+//!
+//! The golden SVGs are generated with the default features. Without `shaping`, text is
+//! measured without kerning, so widths differ slightly and these tests are skipped.
+//! Their text only uses characters the bundled fonts have, so `system-fonts` doesn't
+//! change them; keep it that way when adding cases.
+#![cfg(feature = "shaping")]
+
+mod support;
+
+use rsbadges::{Badge, Segment};
+use std::fs;
+use std::path::PathBuf;
+
+/// Set this environment variable to overwrite the golden SVGs with the current output.
+const BLESS_VAR: &str = "RSBADGES_BLESS";
+
+/// The suffix of every ID in the golden SVGs.
+const ID_SUFFIX: &str = "zujxzBq";
+
+fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots")
+}
+
+/// Generate `badge` in every style and compare each against `<style>_<case>.svg`.
+fn assert_snapshots(case: &str, badge: Badge) {
+    let bless = std::env::var_os(BLESS_VAR).is_some();
+    let mut mismatches = Vec::new();
+
+    for (style_name, style) in support::named_styles(&badge) {
+        let svg = style.generate_svg_with_id_suffix(ID_SUFFIX).unwrap();
+        let path = snapshot_dir().join(format!("{}_{}.svg", style_name, case));
+
+        if bless {
+            fs::create_dir_all(snapshot_dir()).unwrap();
+            fs::write(&path, &svg).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&path) {
+            Ok(golden) if golden == svg => {}
+            Ok(golden) => {
+                let at = golden
+                    .chars()
+                    .zip(svg.chars())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                mismatches.push(format!(
+                    "{}: differs at char {}\n  golden: {}\n  actual: {}",
+                    path.display(),
+                    at,
+                    golden.chars().skip(at).take(80).collect::<String>(),
+                    svg.chars().skip(at).take(80).collect::<String>()
+                ));
+            }
+            Err(_) => mismatches.push(format!("{}: missing", path.display())),
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "{}\n\nRun with {}=1 to accept the new output.",
+        mismatches.join("\n"),
+        BLESS_VAR
+    );
+}
+
+#[test]
+fn snapshot_default() {
+    assert_snapshots("default", Badge::default());
+}
+
+#[test]
+fn snapshot_labels() {
+    assert_snapshots(
+        "accented",
+        Badge {
+            label_text: String::from("Déjà vu"),
+            msg_text: String::from("naïve café"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "no_label",
+        Badge {
+            label_text: String::from(""),
+            msg_text: String::from("passing"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "no_msg",
+        Badge {
+            label_text: String::from("stars"),
+            msg_text: String::from(""),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "escaped",
+        Badge {
+            label_text: String::from("R&D"),
+            msg_text: String::from("<\"quoted\">"),
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn snapshot_logos() {
+    assert_snapshots(
+        "logo",
+        Badge {
+            label_text: String::from("rust"),
+            msg_text: String::from("1.70"),
+            logo: String::from("https://simpleicons.org/icons/rust.svg"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "logo_only",
+        Badge {
+            label_text: String::from(""),
+            msg_text: String::from("rust"),
+            logo: String::from("https://simpleicons.org/icons/rust.svg"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "logo_as_label",
+        Badge {
+            label_text: String::from(""),
+            msg_text: String::from("rust"),
+            logo: String::from("https://simpleicons.org/icons/rust.svg"),
+            use_logo_as_label: true,
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn snapshot_links() {
+    assert_snapshots(
+        "side_links",
+        Badge {
+            label_link: String::from("https://example.com/label"),
+            msg_link: String::from("https://example.com/msg?a=1&b=2"),
+            label_title: String::from("Label"),
+            msg_title: String::from("Message"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "badge_link",
+        Badge {
+            label_link: String::from("https://example.com/label"),
+            badge_link: String::from("https://example.com"),
+            badge_title: String::from("Whole badge"),
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn snapshot_colors() {
+    assert_snapshots(
+        "light_colors",
+        Badge {
+            label_color: String::from("yellow"),
+            msg_color: String::from("#f0f0f0"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "css_colors",
+        Badge {
+            label_color: String::from("hsl(15, 100%, 50%)"),
+            msg_color: String::from("rgb(40, 20, 50)"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "gradient",
+        Badge {
+            label_color: String::from("linear-gradient(#555, rgba(0, 0, 0, 0.5))"),
+            msg_color: String::from("linear-gradient(to right, #8a2be2, red 70%, orange)"),
+            ..Badge::default()
+        },
+    );
+    assert_snapshots(
+        "dark_mode",
+        Badge {
+            dark_label_color: String::from("#0d1117"),
+            dark_msg_color: String::from("#238636"),
+            dark_label_text_color: String::from("#8b949e"),
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn snapshot_segments() {
+    assert_snapshots(
+        "segments",
+        Badge::from_segments(vec![
+            Segment::new("build"),
+            Segment {
+                link: String::from("https://example.com/linux"),
+                ..Segment::new("linux")
+            },
+            Segment {
+                color: String::from("#4c1"),
+                title: String::from("Status"),
+                ..Segment::new("passing")
+            },
+            Segment {
+                color: String::from("linear-gradient(to right, #555, #999)"),
+                dark_color: String::from("#30363d"),
+                ..Segment::new("2m31s")
+            },
+        ]),
+    );
+}
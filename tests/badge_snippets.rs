@@ -0,0 +1,122 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Markup snippets that show a badge
+
+use rsbadges::{Badge, BadgeGrid, SnippetFormat, Style};
+
+fn linked_badge() -> Badge {
+    Badge {
+        label_text: String::from("build"),
+        msg_text: String::from("passing"),
+        badge_link: String::from("https://ci.example.com/?a=1&b=2"),
+        ..Badge::default()
+    }
+}
+
+#[test]
+fn snippets_in_every_format() {
+    let badge = linked_badge();
+    assert_eq!(
+        badge.snippet(SnippetFormat::Markdown, "badges/build.svg"),
+        "[![build: passing](badges/build.svg)](https://ci.example.com/?a=1&b=2)"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::Html, "badges/build.svg"),
+        "<a href=\"https://ci.example.com/?a=1&amp;b=2\">\
+        <img src=\"badges/build.svg\" alt=\"build: passing\"></a>"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::ReStructuredText, "badges/build.svg"),
+        ".. image:: badges/build.svg\n   :alt: build: passing\n   :target: https://ci.example.com/?a=1&b=2"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::AsciiDoc, "badges/build.svg"),
+        "image:badges/build.svg[\"build: passing\",link=\"https://ci.example.com/?a=1&b=2\"]"
+    );
+}
+
+#[test]
+fn snippet_alt_text() {
+    // The badge title takes over from the label and message
+    let badge = Badge {
+        badge_title: String::from("Build status"),
+        badge_link: String::new(),
+        ..linked_badge()
+    };
+    assert_eq!(
+        badge.snippet(SnippetFormat::Markdown, "build.svg"),
+        "![Build status](build.svg)"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::ReStructuredText, "build.svg"),
+        ".. image:: build.svg\n   :alt: Build status"
+    );
+}
+
+#[test]
+fn snippets_are_escaped() {
+    let badge = Badge {
+        label_text: String::from("[docs]"),
+        msg_text: String::from("\"latest\" <3"),
+        badge_link: String::new(),
+        ..Badge::default()
+    };
+    assert_eq!(
+        badge.snippet(SnippetFormat::Markdown, "my badges/docs (latest).svg"),
+        "![\\[docs\\]: \"latest\" <3](my%20badges/docs%20%28latest%29.svg)"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::Html, "docs.svg"),
+        "<img src=\"docs.svg\" alt=\"[docs]: &quot;latest&quot; &lt;3\">"
+    );
+    assert_eq!(
+        badge.snippet(SnippetFormat::AsciiDoc, "docs.svg"),
+        "image:docs.svg[\"[docs]: \\\"latest\\\" <3\"]"
+    );
+}
+
+#[test]
+fn grid_snippet() {
+    let grid = BadgeGrid {
+        badges: vec![
+            Style::Flat(linked_badge()),
+            Style::Social(Badge {
+                label_text: String::from("stars"),
+                msg_text: String::from("42"),
+                ..Badge::default()
+            }),
+        ],
+        ..BadgeGrid::default()
+    };
+    assert_eq!(
+        grid.snippet(SnippetFormat::Markdown, "header.svg"),
+        "![build: passing, stars: 42](header.svg)"
+    );
+}
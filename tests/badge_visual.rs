@@ -0,0 +1,121 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Compare rasterized badges against the reference PNGs in `tests/references`.
+//!
+//! Like the snapshot tests, the references are rendered with the default features, so
+//! these tests are skipped without `shaping`, and their text only uses characters the
+//! bundled fonts have.
+#![cfg(feature = "shaping")]
+
+mod support;
+
+use rsbadges::Badge;
+use std::path::PathBuf;
+
+fn local_logo() -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/rust.svg");
+    String::from(path.to_str().unwrap())
+}
+
+/// Render `badge` in every style and compare each against `<style>_<case>.png`.
+fn assert_looks_like_reference(case: &str, badge: Badge) {
+    let failures: Vec<String> = support::named_styles(&badge)
+        .into_iter()
+        .filter_map(|(style_name, style)| {
+            let svg = style.generate_svg().unwrap();
+            support::visual::compare_to_reference(&format!("{}_{}", style_name, case), &svg).err()
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn visual_text() {
+    assert_looks_like_reference(
+        "text",
+        Badge {
+            label_text: String::from("build"),
+            msg_text: String::from("passing"),
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn visual_wide_text() {
+    assert_looks_like_reference(
+        "wide_text",
+        Badge {
+            label_text: String::from("WWW MMM"),
+            msg_text: String::from("Déjà vu, naïve café"),
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn visual_logo() {
+    assert_looks_like_reference(
+        "logo",
+        Badge {
+            label_text: String::from("rust"),
+            msg_text: String::from("1.70"),
+            logo: local_logo(),
+            embed_logo: true,
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn visual_logo_only() {
+    assert_looks_like_reference(
+        "logo_only",
+        Badge {
+            label_text: String::from(""),
+            msg_text: String::from("rust"),
+            logo: local_logo(),
+            embed_logo: true,
+            ..Badge::default()
+        },
+    );
+}
+
+#[test]
+fn visual_light_colors() {
+    assert_looks_like_reference(
+        "light_colors",
+        Badge {
+            label_color: String::from("yellow"),
+            msg_color: String::from("#f0f0f0"),
+            ..Badge::default()
+        },
+    );
+}
@@ -0,0 +1,123 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+use rsbadges::{Badge, Style};
+
+/// Width of the whole badge, read from the root SVG element.
+pub fn svg_width(style: Style) -> f32 {
+    let svg = style.generate_svg().unwrap();
+    let start = svg.find("width=\"").unwrap() + "width=\"".len();
+    let end = start + svg[start..].find('"').unwrap();
+    svg[start..end].parse().unwrap()
+}
+
+pub fn label_badge(label_text: &str) -> Badge {
+    Badge {
+        label_text: String::from(label_text),
+        ..Badge::default()
+    }
+}
+
+/// Check how much a "for the badge" label grows when one more of `text` is added.
+///
+/// Flat badges measure their label with the same font, without letter spacing and at
+/// 17px instead of 14px, so the growth of a flat badge gives the glyph advance. The "for
+/// the badge" label should grow by that advance plus exactly one letter spacing (2px),
+/// no matter how many bytes the added text takes up.
+fn assert_one_letter_spacing_per_grapheme(text: &str) {
+    let once = text.to_string();
+    let twice = text.repeat(2);
+
+    let flat_growth =
+        svg_width(Style::Flat(label_badge(&twice))) - svg_width(Style::Flat(label_badge(&once)));
+    let forthebadge_growth = svg_width(Style::ForTheBadge(label_badge(&twice)))
+        - svg_width(Style::ForTheBadge(label_badge(&once)));
+
+    let expected = (flat_growth * 14.0 / 17.0) + 2.0;
+    assert!(
+        (forthebadge_growth - expected).abs() < 0.01,
+        "{:?}: grew by {}, expected {}",
+        text,
+        forthebadge_growth,
+        expected
+    );
+}
+
+#[test]
+fn letter_spacing_ascii() {
+    assert_one_letter_spacing_per_grapheme("A");
+}
+
+#[test]
+fn letter_spacing_accented() {
+    assert_one_letter_spacing_per_grapheme("É");
+    // Decomposed, with no precomposed form: two code points, one grapheme
+    assert_one_letter_spacing_per_grapheme("Q\u{307}");
+}
+
+#[test]
+fn letter_spacing_cjk() {
+    assert_one_letter_spacing_per_grapheme("版");
+}
+
+#[test]
+fn accented_text_matches_ascii_width() {
+    // Accented capitals share the advance of their base letter
+    for style in [Style::Flat, Style::ForTheBadge, Style::Social] {
+        assert_eq!(
+            svg_width(style(label_badge("ROCK DOTS"))),
+            svg_width(style(label_badge("RÖCK DÖTS")))
+        );
+    }
+}
+
+#[test]
+fn normalization_does_not_change_width() {
+    // Precomposed and decomposed forms of the same text are measured identically
+    for style in [Style::Flat, Style::ForTheBadge, Style::Social] {
+        assert_eq!(
+            svg_width(style(label_badge("r\u{f6}ck"))),
+            svg_width(style(label_badge("ro\u{308}ck")))
+        );
+    }
+}
+
+#[test]
+fn widths_grow_linearly() {
+    // With no rounding of text widths, each repetition adds exactly the same width
+    for text in ["l", "m", "0"] {
+        let widths = (1..6)
+            .map(|n| svg_width(Style::Flat(label_badge(&text.repeat(n)))))
+            .collect::<Vec<_>>();
+        let growth = widths[1] - widths[0];
+        for pair in widths.windows(2) {
+            assert!((pair[1] - pair[0] - growth).abs() < 0.001);
+        }
+    }
+}
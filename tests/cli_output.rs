@@ -0,0 +1,222 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! What the CLI writes to stdout and stderr, and its exit codes
+
+use std::process::{Command, Output};
+
+fn rsbadges(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rsbadges"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn text(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn stdout_holds_only_the_svg() {
+    let output = rsbadges(&["--label", "build", "--msg", "passing"]);
+    assert!(output.status.success());
+    let stdout = text(&output.stdout);
+    assert!(stdout.starts_with("<svg"), "{}", stdout);
+    assert!(stdout.trim_end().ends_with("</svg>"), "{}", stdout);
+    assert_eq!(text(&output.stderr), "");
+}
+
+#[test]
+fn diagnostics_go_to_stderr() {
+    let dir = std::env::temp_dir().join(format!("rsbadges-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("build.svg");
+    let path = path.to_str().unwrap();
+
+    // A saved badge is not written to stdout, unless asked for
+    let output = rsbadges(&["--label", "build", "--save-to-svg-at", path]);
+    assert!(output.status.success());
+    assert_eq!(text(&output.stdout), "");
+    assert_eq!(
+        text(&output.stderr),
+        format!("Saved the badge to {}\n", path)
+    );
+
+    let output = rsbadges(&["--save-to-svg-at", path, "--output", "svg", "--quiet"]);
+    assert!(output.status.success());
+    assert_eq!(text(&output.stdout), std::fs::read_to_string(path).unwrap());
+    assert_eq!(text(&output.stderr), "");
+
+    let output = rsbadges(&["--save-to-svg-at", path, "--verbose"]);
+    assert!(output.status.success());
+    assert!(text(&output.stderr).lines().count() > 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn value_badges() {
+    let output = rsbadges(&["--label", "coverage", "--value", "87.3"]);
+    assert!(output.status.success());
+    assert!(text(&output.stdout).contains("coverage: 87.3%"));
+}
+
+#[test]
+fn exit_codes_tell_errors_apart() {
+    let cases: [(&[&str], i32); 7] = [
+        (&["--quiet", "--verbose"], 2),
+        (&["--value", "87.3"], 2),
+        (
+            &[
+                "--label",
+                "cov",
+                "--value",
+                "87",
+                "--scale",
+                "0:red,inf:green",
+            ],
+            5,
+        ),
+        (
+            &[
+                "--label",
+                "cov",
+                "--value",
+                "87",
+                "--scale",
+                "nan:red,80:green",
+            ],
+            5,
+        ),
+        (&["--style", "round"], 3),
+        (&["--msg-color", "not-a-color"], 4),
+        (&["grid", "missing-badges.txt"], 7),
+    ];
+    for (args, code) in cases {
+        let output = rsbadges(args);
+        assert_eq!(output.status.code(), Some(code), "{:?}", args);
+        assert_eq!(text(&output.stdout), "", "{:?}", args);
+        assert!(text(&output.stderr).starts_with("Error: "), "{:?}", args);
+    }
+}
+
+#[test]
+fn grid_options_are_checked() {
+    let dir = std::env::temp_dir().join(format!("rsbadges-grid-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let list = dir.join("badges.txt");
+    let list = list.to_str().unwrap();
+
+    std::fs::write(list, "--label a\n--label b\n").unwrap();
+    let output = rsbadges(&["grid", list, "--columns", "1"]);
+    assert!(output.status.success());
+    for columns in ["1.5", "-1", "two"] {
+        let output = rsbadges(&["grid", list, "--columns", columns]);
+        assert_eq!(output.status.code(), Some(2), "{}", columns);
+    }
+
+    // Options for the whole run can't be given to a single badge of the file
+    for option in ["--save-to-svg-at out.svg", "--output svg", "--quiet"] {
+        std::fs::write(list, format!("--label a\n--label b {}\n", option)).unwrap();
+        let output = rsbadges(&["grid", list]);
+        assert_eq!(output.status.code(), Some(2), "{}", option);
+        let stderr = text(&output.stderr);
+        assert!(stderr.contains(&format!("{}:2: ", list)), "{}", stderr);
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn help_and_usage() {
+    let output = rsbadges(&["--help"]);
+    assert!(output.status.success());
+    assert!(text(&output.stdout).starts_with("Usage: "));
+    assert_eq!(text(&output.stderr), "");
+
+    // Without arguments, the usage is an error
+    let output = rsbadges(&[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(text(&output.stdout), "");
+    assert!(text(&output.stderr).starts_with("Usage: "));
+}
+
+#[test]
+fn readme_update_options_are_checked() {
+    let dir = std::env::temp_dir().join(format!("rsbadges-readme-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let readme = dir.join("README.md");
+    let readme = readme.to_str().unwrap();
+    let list = dir.join("badges.txt");
+    let list = list.to_str().unwrap();
+    let badge = dir.join("build.svg");
+    std::fs::write(readme, "<!-- rsbadges:start -->\n<!-- rsbadges:end -->\n").unwrap();
+    std::fs::write(
+        list,
+        format!("--label build --save-to-svg-at {}\n", badge.display()),
+    )
+    .unwrap();
+
+    let output = rsbadges(&[
+        "readme-update",
+        readme,
+        list,
+        "--quiet",
+        "--snippet",
+        "html",
+    ]);
+    assert!(output.status.success(), "{}", text(&output.stderr));
+    assert!(badge.exists());
+
+    // Options that readme-update would not honour are errors, not ignored
+    let cases: [&[&str]; 6] = [
+        &["--save-to-svg-at", "other.svg"],
+        &["--dark-pair"],
+        &["--open-in-browser"],
+        &["--snippet-url", "other.svg"],
+        &["--output", "svg"],
+        &["--label", "tests"],
+    ];
+    // Without the feature, --validate is an unknown option instead
+    let validate: &[&[&str]] = if cfg!(feature = "validate") {
+        &[&["--validate"]]
+    } else {
+        &[]
+    };
+    for &options in cases.iter().chain(validate) {
+        let mut args = vec!["readme-update", readme, list];
+        args.extend(options);
+        let output = rsbadges(&args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", options);
+        let stderr = text(&output.stderr);
+        assert!(stderr.contains(options[0]), "{}", stderr);
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,91 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Updating the badge block of a README
+
+use rsbadges::{update_readme_block, BadgeError};
+
+const README: &str =
+    "# Project\n\n<!-- rsbadges:start -->\nold badges\n<!-- rsbadges:end -->\n\nText\n";
+
+#[test]
+fn replaces_only_the_block() {
+    let updated = update_readme_block(README, "![a: b](a.svg)\n![c: d](c.svg)").unwrap();
+    assert_eq!(
+        updated,
+        "# Project\n\n<!-- rsbadges:start -->\n![a: b](a.svg)\n![c: d](c.svg)\n<!-- rsbadges:end -->\n\nText\n"
+    );
+}
+
+#[test]
+fn updating_twice_changes_nothing() {
+    let once = update_readme_block(README, "![a: b](a.svg)").unwrap();
+    let twice = update_readme_block(&once, "![a: b](a.svg)").unwrap();
+    assert_eq!(once, twice);
+
+    // Markers on the same line get their own lines once
+    let inline = "<!-- rsbadges:start --><!-- rsbadges:end -->";
+    let once = update_readme_block(inline, "![a: b](a.svg)").unwrap();
+    assert_eq!(
+        once,
+        "<!-- rsbadges:start -->\n![a: b](a.svg)\n<!-- rsbadges:end -->"
+    );
+    assert_eq!(update_readme_block(&once, "![a: b](a.svg)").unwrap(), once);
+}
+
+#[test]
+fn keeps_crlf_line_endings() {
+    let readme = README.replace('\n', "\r\n");
+    let updated = update_readme_block(&readme, "![a: b](a.svg)\n![c: d](c.svg)").unwrap();
+    assert_eq!(
+        updated,
+        "# Project\r\n\r\n<!-- rsbadges:start -->\r\n![a: b](a.svg)\r\n![c: d](c.svg)\r\n\
+        <!-- rsbadges:end -->\r\n\r\nText\r\n"
+    );
+}
+
+#[test]
+fn markers_must_appear_once_and_in_order() {
+    let invalid = [
+        "# Project\n<!-- rsbadges:end -->\n",
+        "# Project\n<!-- rsbadges:start -->\n",
+        "<!-- rsbadges:start -->\n<!-- rsbadges:start -->\n<!-- rsbadges:end -->\n",
+        "<!-- rsbadges:end -->\n<!-- rsbadges:start -->\n",
+    ];
+    for readme in invalid {
+        assert!(
+            matches!(
+                update_readme_block(readme, "![a: b](a.svg)"),
+                Err(BadgeError::InvalidReadme(_))
+            ),
+            "{:?}",
+            readme
+        );
+    }
+}
@@ -0,0 +1,127 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Coverage badges from the reports in tests/reports
+#![cfg(feature = "reports")]
+
+use rsbadges::{BadgeError, ColorScale, Coverage, CoverageFormat, Style};
+use std::path::PathBuf;
+
+fn report_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/reports")
+        .join(name)
+}
+
+fn coverage(lines_covered: u64, lines_valid: u64) -> Coverage {
+    Coverage {
+        lines_covered,
+        lines_valid,
+    }
+}
+
+#[test]
+fn coverage_from_lcov() {
+    // The second record has no LF/LH totals, so its DA lines are counted
+    let coverage = Coverage::from_report(report_path("lcov.info")).unwrap();
+    assert_eq!(coverage, self::coverage(5, 8));
+    assert_eq!(coverage.percent(), 62.5);
+}
+
+#[test]
+fn coverage_from_cobertura() {
+    let coverage = Coverage::from_report(report_path("cobertura.xml")).unwrap();
+    assert_eq!(coverage, self::coverage(171, 200));
+}
+
+#[test]
+fn coverage_from_cobertura_without_totals() {
+    // Lines listed under methods repeat those of their class
+    let coverage = Coverage::from_report(report_path("cobertura-no-totals.xml")).unwrap();
+    assert_eq!(coverage, self::coverage(2, 4));
+}
+
+#[test]
+fn coverage_from_tarpaulin() {
+    let coverage = Coverage::from_report(report_path("tarpaulin-report.json")).unwrap();
+    assert_eq!(coverage, self::coverage(3, 4));
+
+    // Older reports only have totals for each file
+    let report = r#"{"files":[{"covered":2,"coverable":3},{"covered":1,"coverable":1}]}"#;
+    let coverage = Coverage::parse(report, CoverageFormat::Tarpaulin).unwrap();
+    assert_eq!(coverage, self::coverage(3, 4));
+}
+
+#[test]
+fn coverage_format_detection() {
+    let detect =
+        |name| CoverageFormat::detect(&std::fs::read_to_string(report_path(name)).unwrap());
+    assert_eq!(detect("lcov.info"), Some(CoverageFormat::Lcov));
+    assert_eq!(detect("cobertura.xml"), Some(CoverageFormat::Cobertura));
+    assert_eq!(
+        detect("tarpaulin-report.json"),
+        Some(CoverageFormat::Tarpaulin)
+    );
+    assert_eq!(CoverageFormat::detect("coverage: 87%"), None);
+}
+
+#[test]
+fn coverage_badge() {
+    let badge = coverage(171, 200).badge(&ColorScale::coverage()).unwrap();
+    assert_eq!(badge.label_text, "coverage");
+    assert_eq!(badge.msg_text, "85.5%");
+    assert_eq!(badge.msg_color, "#97ca00");
+    let svg = Style::Flat(badge).generate_svg().unwrap();
+    assert!(svg.contains("aria-label=\"coverage: 85.5%\""));
+}
+
+#[test]
+fn coverage_report_errors() {
+    assert!(matches!(
+        Coverage::from_report(report_path("missing.info")),
+        Err(BadgeError::CannotReadFile(_))
+    ));
+    assert!(matches!(
+        Coverage::parse("SF:src/lib.rs\nDA:1,x\n", CoverageFormat::Lcov),
+        Err(BadgeError::InvalidReport(_))
+    ));
+    assert!(matches!(
+        Coverage::parse("<coverage", CoverageFormat::Cobertura),
+        Err(BadgeError::InvalidReport(_))
+    ));
+    assert!(matches!(
+        Coverage::parse("{}", CoverageFormat::Tarpaulin),
+        Err(BadgeError::InvalidReport(_))
+    ));
+    // A report without any lines would read as 0%
+    assert!(matches!(
+        Coverage::parse("TN:\nend_of_record\n", CoverageFormat::Lcov),
+        Err(BadgeError::InvalidReport(_))
+    ));
+}
@@ -0,0 +1,197 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Git metadata badges from a repository built in a temporary directory
+#![cfg(feature = "reports")]
+
+use rsbadges::{BadgeError, GitField, GitMetadata, Style};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn git(repository: &Path, args: &[&str], author: &str, date: &str) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repository)
+        .args(args)
+        .env("GIT_AUTHOR_NAME", author)
+        .env("GIT_AUTHOR_EMAIL", format!("{}@example.com", author))
+        .env("GIT_COMMITTER_NAME", author)
+        .env("GIT_COMMITTER_EMAIL", format!("{}@example.com", author))
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// A new repository with the commits given as (author, date), tagged v0.1.0 after
+/// the commit at index `tag_after`, if any.
+fn repository(name: &str, commits: &[(&str, &str)], tag_after: Option<usize>) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rsbadges-git-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    git(
+        &dir,
+        &["init", "--quiet", "--initial-branch=main"],
+        "a",
+        "2021-01-01T00:00:00",
+    );
+    for (i, (author, date)) in commits.iter().enumerate() {
+        let message = format!("Commit {}", i);
+        git(
+            &dir,
+            &["commit", "--quiet", "--allow-empty", "-m", &message],
+            author,
+            date,
+        );
+        if tag_after == Some(i) {
+            git(&dir, &["tag", "v0.1.0"], author, date);
+        }
+    }
+    dir
+}
+
+#[test]
+fn git_metadata_since_tag() {
+    let dir = repository(
+        "tagged",
+        &[
+            ("ada", "2021-03-01T12:00:00"),
+            ("grace", "2021-03-02T12:00:00"),
+            ("ada", "2021-03-03T12:00:00"),
+            ("linus", "2021-03-04T12:00:00"),
+        ],
+        Some(1),
+    );
+    let repository = GitMetadata::from_repository(&dir).unwrap();
+    assert_eq!(
+        repository,
+        GitMetadata {
+            tag: Some(String::from("v0.1.0")),
+            commits_since_tag: 2,
+            last_commit: String::from("2021-03-04"),
+            branch: Some(String::from("main")),
+            contributors: 3,
+        }
+    );
+
+    let badge = repository.badge(GitField::CommitsSinceTag).unwrap();
+    assert_eq!(badge.label_text, "commits since v0.1.0");
+    assert_eq!(badge.msg_text, "2");
+    assert!(Style::Flat(badge).generate_svg().is_ok());
+    assert_eq!(repository.badge(GitField::Tag).unwrap().msg_text, "v0.1.0");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn git_metadata_without_tags() {
+    let dir = repository(
+        "untagged",
+        &[
+            ("ada", "2021-03-01T12:00:00"),
+            ("ada", "2021-03-02T12:00:00"),
+        ],
+        None,
+    );
+    let repository = GitMetadata::from_repository(&dir).unwrap();
+    let badge = repository.badge(GitField::CommitsSinceTag).unwrap();
+    assert_eq!(badge.label_text, "commits");
+    assert_eq!(badge.msg_text, "2");
+    assert!(matches!(
+        repository.badge(GitField::Tag),
+        Err(BadgeError::InvalidRepository(_))
+    ));
+
+    // A detached HEAD has no branch
+    git(
+        &dir,
+        &["checkout", "--quiet", "--detach"],
+        "ada",
+        "2021-03-02T12:00:00",
+    );
+    let repository = GitMetadata::from_repository(&dir).unwrap();
+    assert_eq!(repository.branch, None);
+    assert!(matches!(
+        repository.badge(GitField::Branch),
+        Err(BadgeError::InvalidRepository(_))
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn empty_repositories_are_rejected() {
+    let dir = repository("empty", &[], None);
+    assert!(matches!(
+        GitMetadata::from_repository(&dir),
+        Err(BadgeError::InvalidRepository(_))
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn tags_are_not_read_as_options_or_branches() {
+    let dir = repository(
+        "odd-tags",
+        &[
+            ("ada", "2021-03-01T12:00:00"),
+            ("ada", "2021-03-02T12:00:00"),
+            ("ada", "2021-03-03T12:00:00"),
+        ],
+        None,
+    );
+    // git tag refuses names starting with a dash, but the ref itself is valid
+    git(
+        &dir,
+        &["update-ref", "refs/tags/-v1", "HEAD~1"],
+        "ada",
+        "2021-03-03T12:00:00",
+    );
+    let repository = GitMetadata::from_repository(&dir).unwrap();
+    assert_eq!(repository.tag.as_deref(), Some("-v1"));
+    assert_eq!(repository.commits_since_tag, 1);
+
+    // A branch named like the tag, pointing elsewhere, doesn't change the count
+    git(
+        &dir,
+        &["update-ref", "-d", "refs/tags/-v1"],
+        "ada",
+        "2021-03-03T12:00:00",
+    );
+    git(&dir, &["tag", "v1", "HEAD~2"], "ada", "2021-03-03T12:00:00");
+    git(
+        &dir,
+        &["branch", "v1", "HEAD"],
+        "ada",
+        "2021-03-03T12:00:00",
+    );
+    let repository = GitMetadata::from_repository(&dir).unwrap();
+    assert_eq!(repository.tag.as_deref(), Some("v1"));
+    assert_eq!(repository.commits_since_tag, 2);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,110 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Crate metadata badges from the workspace in tests/manifests
+#![cfg(feature = "reports")]
+
+use rsbadges::{BadgeError, CrateField, CrateMetadata, Style};
+use std::path::PathBuf;
+
+fn manifest_path(path: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/manifests/workspace")
+        .join(path)
+}
+
+fn workspace() -> Vec<CrateMetadata> {
+    CrateMetadata::from_manifest(manifest_path("Cargo.toml")).unwrap()
+}
+
+#[test]
+fn workspace_members_in_order() {
+    // crates/scratch is excluded from the workspace
+    let names: Vec<_> = workspace().into_iter().map(|krate| krate.name).collect();
+    assert_eq!(names, ["demo-cli", "demo-core"]);
+}
+
+#[test]
+fn inherited_crate_metadata() {
+    let core = &workspace()[1];
+    assert_eq!(core.version.as_deref(), Some("0.4.2"));
+    assert_eq!(core.license.as_deref(), Some("MIT OR Apache-2.0"));
+    assert_eq!(core.rust_version.as_deref(), Some("1.70"));
+    assert_eq!(core.edition, "2021");
+
+    let cli = &workspace()[0];
+    assert_eq!(cli.version.as_deref(), Some("0.5.0"));
+    assert_eq!(cli.rust_version, None);
+    assert_eq!(cli.edition, "2018");
+}
+
+#[test]
+fn member_manifest_finds_its_workspace() {
+    let crates = CrateMetadata::from_manifest(manifest_path("crates/core/Cargo.toml")).unwrap();
+    assert_eq!(crates.len(), 1);
+    assert_eq!(crates[0], workspace()[1]);
+}
+
+#[test]
+fn dependency_counts() {
+    // Dev dependencies aren't counted, target-specific ones are
+    let core = &workspace()[1];
+    assert_eq!(core.dependencies, 3);
+    assert_eq!(core.locked_dependencies, Some(4));
+
+    let badge = workspace()[0].badge(CrateField::Dependencies).unwrap();
+    assert_eq!(badge.label_text, "dependencies");
+    assert_eq!(badge.msg_text, "2 direct, 5 total");
+}
+
+#[test]
+fn crate_field_badges() {
+    let core = &workspace()[1];
+    let message = |field| core.badge(field).unwrap().msg_text;
+    assert_eq!(message(CrateField::Version), "v0.4.2");
+    assert_eq!(message(CrateField::License), "MIT OR Apache-2.0");
+    assert_eq!(message(CrateField::RustVersion), "1.70");
+    assert_eq!(message(CrateField::Edition), "2021");
+    assert!(Style::Flat(core.badge(CrateField::Version).unwrap())
+        .generate_svg()
+        .is_ok());
+}
+
+#[test]
+fn missing_crate_fields_are_rejected() {
+    let cli = &workspace()[0];
+    assert!(matches!(
+        cli.badge(CrateField::RustVersion),
+        Err(BadgeError::InvalidManifest(_))
+    ));
+    assert!(matches!(
+        CrateMetadata::from_manifest(manifest_path("missing/Cargo.toml")),
+        Err(BadgeError::CannotReadFile(_))
+    ));
+}
@@ -0,0 +1,115 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Test result badges from the reports in tests/reports
+#![cfg(feature = "reports")]
+
+use rsbadges::{BadgeError, Style, TestReportFormat, TestResults};
+use std::path::PathBuf;
+
+fn report_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/reports")
+        .join(name)
+}
+
+fn results(passed: u64, failed: u64, skipped: u64) -> TestResults {
+    TestResults {
+        passed,
+        failed,
+        skipped,
+    }
+}
+
+#[test]
+fn test_results_from_junit() {
+    // Errors count as failures
+    let results = TestResults::from_report(report_path("junit.xml")).unwrap();
+    assert_eq!(results, self::results(3, 2, 1));
+}
+
+#[test]
+fn test_results_from_libtest() {
+    // Cargo's own output between the JSON lines is ignored
+    let results = TestResults::from_report(report_path("libtest.json")).unwrap();
+    assert_eq!(results, self::results(3, 1, 1));
+}
+
+#[test]
+fn test_results_from_unfinished_libtest_run() {
+    let report = "{ \"type\": \"suite\", \"event\": \"started\", \"test_count\": 2 }\n\
+        { \"type\": \"test\", \"name\": \"a\", \"event\": \"ok\" }\n\
+        { \"type\": \"test\", \"name\": \"b\", \"event\": \"failed\" }\n";
+    let results = TestResults::parse(report, TestReportFormat::Libtest).unwrap();
+    assert_eq!(results, self::results(1, 1, 0));
+}
+
+#[test]
+fn test_report_format_detection() {
+    assert_eq!(
+        TestReportFormat::detect("<?xml version=\"1.0\"?><testsuites/>"),
+        Some(TestReportFormat::JUnit)
+    );
+    assert_eq!(
+        TestReportFormat::detect("running 1 test\n{ \"type\": \"suite\" }"),
+        Some(TestReportFormat::Libtest)
+    );
+    assert_eq!(TestReportFormat::detect("test result: ok."), None);
+}
+
+#[test]
+fn test_results_badge() {
+    let badge = results(412, 3, 5).badge(false);
+    assert_eq!(badge.label_text, "tests");
+    assert_eq!(badge.msg_text, "412 passed, 3 failed");
+    assert_eq!(badge.msg_color, "#e05d44");
+
+    let badge = results(412, 0, 5).badge(true);
+    assert_eq!(badge.msg_text, "412 passed, 5 skipped");
+    assert_eq!(badge.msg_color, "#4c1");
+    assert!(Style::Flat(badge).generate_svg().is_ok());
+
+    assert_eq!(results(412, 0, 0).badge(true).msg_text, "412 passed");
+}
+
+#[test]
+fn test_reports_without_tests_are_rejected() {
+    assert!(matches!(
+        TestResults::parse("<testsuites/>", TestReportFormat::JUnit),
+        Err(BadgeError::InvalidReport(_))
+    ));
+    assert!(matches!(
+        TestResults::parse("<coverage/>", TestReportFormat::JUnit),
+        Err(BadgeError::InvalidReport(_))
+    ));
+    assert!(matches!(
+        TestResults::from_report(report_path("lcov.info")),
+        Err(BadgeError::InvalidReport(_))
+    ));
+}
@@ -0,0 +1,57 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Helpers shared by the test suites
+
+// Each suite uses only some of the helpers
+#![allow(dead_code)]
+
+pub mod visual;
+
+use rsbadges::{Badge, Style};
+
+/// `badge` in every style.
+pub fn all_styles(badge: &Badge) -> Vec<Style> {
+    named_styles(badge)
+        .into_iter()
+        .map(|(_, style)| style)
+        .collect()
+}
+
+/// `badge` in every style, with the name each style gives to its snapshot and
+/// reference files.
+pub fn named_styles(badge: &Badge) -> Vec<(&'static str, Style)> {
+    vec![
+        ("flat", Style::Flat(badge.clone())),
+        ("flat_square", Style::FlatSquare(badge.clone())),
+        ("plastic", Style::Plastic(badge.clone())),
+        ("forthebadge", Style::ForTheBadge(badge.clone())),
+        ("social", Style::Social(badge.clone())),
+    ]
+}
@@ -0,0 +1,176 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2021 RSBadges Authors
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice,
+//    this list of conditions and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice,
+//    this list of conditions and the following disclaimer in the documentation
+//    and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors
+//    may be used to endorse or promote products derived from this software
+//    without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED
+// WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+// IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT,
+// INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA,
+// OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE)
+// ARISING IN ANY WAY OUT OF THE USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY
+// OF SUCH DAMAGE.
+
+//! Rasterize badges and compare them against reference PNGs.
+//!
+//! Badges are rendered with resvg, using only the fonts bundled with RSBadges, so the
+//! result doesn't depend on the fonts installed on the machine running the tests. The
+//! comparison tolerates small anti-aliasing differences, but catches text overflowing
+//! its side, logos overlapping text, and other visible layout changes.
+//!
+//! Reference PNGs live in `tests/references`. Run the tests with `RSBADGES_BLESS=1`
+//! to overwrite them with the current rendering.
+
+use resvg::{tiny_skia, usvg};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Set this environment variable to overwrite the reference PNGs.
+const BLESS_VAR: &str = "RSBADGES_BLESS";
+
+/// Badges are rendered at this multiple of their size, so that text is legible.
+const RENDER_SCALE: f32 = 2.0;
+
+/// Two pixels look the same when the YIQ distance between their colors is below this
+/// fraction of the largest possible distance.
+const PIXEL_THRESHOLD: f32 = 0.1;
+
+/// A rendering matches its reference when at most this fraction of its pixels differ.
+const MAX_DIFF_RATIO: f32 = 0.005;
+
+/// Largest possible YIQ distance between two colors, as used by pixelmatch.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+fn reference_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/references")
+}
+
+/// Parse options that resolve every font family to the fonts bundled with RSBadges.
+fn options() -> usvg::Options<'static> {
+    let fonts_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/badge");
+    let mut options = usvg::Options::default();
+    let fontdb = Arc::make_mut(&mut options.fontdb);
+    for font in [
+        "PTSans-Regular.ttf",
+        "PTSans-Bold.ttf",
+        "OpenSans-Regular.ttf",
+    ] {
+        fontdb.load_font_file(fonts_dir.join(font)).unwrap();
+    }
+    fontdb.set_sans_serif_family("PT Sans");
+    options.font_family = String::from("PT Sans");
+    options
+}
+
+/// Render a badge SVG into a pixmap.
+pub fn rasterize(svg: &str) -> tiny_skia::Pixmap {
+    let tree = usvg::Tree::from_str(svg, &options()).unwrap();
+    let size = tree.size().to_int_size().scale_by(RENDER_SCALE).unwrap();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height()).unwrap();
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(RENDER_SCALE, RENDER_SCALE),
+        &mut pixmap.as_mut(),
+    );
+    pixmap
+}
+
+/// Blend a premultiplied pixel over white, as it would appear on a page.
+fn over_white(pixel: tiny_skia::PremultipliedColorU8) -> [f32; 3] {
+    let white = 255.0 - f32::from(pixel.alpha());
+    [
+        f32::from(pixel.red()) + white,
+        f32::from(pixel.green()) + white,
+        f32::from(pixel.blue()) + white,
+    ]
+}
+
+/// Perceptual distance between two colors, weighted by how sensitive the eye is to
+/// brightness, hue, and saturation.
+fn yiq_delta(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let (dr, dg, db) = (a[0] - b[0], a[1] - b[1], a[2] - b[2]);
+    let y = dr * 0.298_895_3 + dg * 0.586_622_5 + db * 0.114_482_23;
+    let i = dr * 0.595_977_99 - dg * 0.274_176_1 - db * 0.321_801_9;
+    let q = dr * 0.211_470_19 - dg * 0.522_617_2 + db * 0.311_147;
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+/// Count the pixels that visibly differ between two renderings of the same size.
+fn count_diff_pixels(actual: &tiny_skia::Pixmap, reference: &tiny_skia::Pixmap) -> usize {
+    let max_delta = MAX_YIQ_DELTA * PIXEL_THRESHOLD * PIXEL_THRESHOLD;
+    actual
+        .pixels()
+        .iter()
+        .zip(reference.pixels())
+        .filter(|(a, r)| yiq_delta(over_white(**a), over_white(**r)) > max_delta)
+        .count()
+}
+
+/// Render `svg` and compare it against `tests/references/<name>.png`.
+///
+/// Returns a description of the problem if the rendering doesn't match. The failed
+/// rendering is saved next to the reference as `<name>.actual.png` for inspection.
+pub fn compare_to_reference(name: &str, svg: &str) -> Result<(), String> {
+    let actual = rasterize(svg);
+    let path = reference_dir().join(format!("{}.png", name));
+
+    if std::env::var_os(BLESS_VAR).is_some() {
+        fs::create_dir_all(reference_dir()).unwrap();
+        actual.save_png(&path).unwrap();
+        return Ok(());
+    }
+
+    let reference = tiny_skia::Pixmap::load_png(&path)
+        .map_err(|e| format!("{}: cannot load reference: {}", path.display(), e))?;
+    let problem = if (actual.width(), actual.height()) != (reference.width(), reference.height()) {
+        Some(format!(
+            "rendered at {}x{}, reference is {}x{}",
+            actual.width(),
+            actual.height(),
+            reference.width(),
+            reference.height()
+        ))
+    } else {
+        let diff_pixels = count_diff_pixels(&actual, &reference);
+        let diff_ratio = diff_pixels as f32 / actual.pixels().len() as f32;
+        (diff_ratio > MAX_DIFF_RATIO).then(|| {
+            format!(
+                "{} pixels ({:.2}%) differ from the reference",
+                diff_pixels,
+                diff_ratio * 100.0
+            )
+        })
+    };
+
+    match problem {
+        None => Ok(()),
+        Some(problem) => {
+            let actual_path = reference_dir().join(format!("{}.actual.png", name));
+            actual.save_png(&actual_path).unwrap();
+            Err(format!(
+                "{}: {}. See {}, or run with {}=1 to accept it.",
+                path.display(),
+                problem,
+                actual_path.display(),
+                BLESS_VAR
+            ))
+        }
+    }
+}